@@ -3,9 +3,18 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 
-use obi_proxy_contract::msg::{AdminResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use obi_proxy_contract::msg::{
+    AdminResponse, ExecuteMsg, FeeDebtHistoryResponse, FeeDebtResponse, InstantiateMsg, MigrateMsg,
+    PendingAdminResponse, QueryMsg, SignersResponse, SimulateExecuteResponse, SpendHistoryResponse,
+};
 
-use obi_proxy_contract::hot_wallet::HotWalletsResponse;
+use obi_proxy_contract::hot_wallet::{HotWalletsResponse, SpendLimitStatusResponse};
+use obi_proxy_contract::proposal::{ProposalResponse, ProposalsResponse};
+use obi_proxy_contract::recovery::{GuardiansResponse, HeirResponse, RecoveryStatusResponse};
+use obi_proxy_contract::scheduled::ScheduledTransfersResponse;
+use obi_proxy_contract::session_key::SessionKeysResponse;
+use obi_proxy_contract::signed::SignedNonceResponse;
+use obi_proxy_contract::subscription::SubscriptionsResponse;
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -18,5 +27,21 @@ fn main() {
     export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
     export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
     export_schema(&schema_for!(AdminResponse), &out_dir);
+    export_schema(&schema_for!(PendingAdminResponse), &out_dir);
+    export_schema(&schema_for!(SignersResponse), &out_dir);
     export_schema(&schema_for!(HotWalletsResponse), &out_dir);
+    export_schema(&schema_for!(SpendLimitStatusResponse), &out_dir);
+    export_schema(&schema_for!(FeeDebtResponse), &out_dir);
+    export_schema(&schema_for!(FeeDebtHistoryResponse), &out_dir);
+    export_schema(&schema_for!(SpendHistoryResponse), &out_dir);
+    export_schema(&schema_for!(SimulateExecuteResponse), &out_dir);
+    export_schema(&schema_for!(ProposalResponse), &out_dir);
+    export_schema(&schema_for!(ProposalsResponse), &out_dir);
+    export_schema(&schema_for!(GuardiansResponse), &out_dir);
+    export_schema(&schema_for!(RecoveryStatusResponse), &out_dir);
+    export_schema(&schema_for!(HeirResponse), &out_dir);
+    export_schema(&schema_for!(SignedNonceResponse), &out_dir);
+    export_schema(&schema_for!(SessionKeysResponse), &out_dir);
+    export_schema(&schema_for!(SubscriptionsResponse), &out_dir);
+    export_schema(&schema_for!(ScheduledTransfersResponse), &out_dir);
 }
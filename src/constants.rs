@@ -15,6 +15,17 @@ pub const TESTNET_ID: &str = "uni-3";
 pub const MAINNET_DENOM: &str = "ujuno";
 pub const TESTNET_DENOM: &str = "ujunox";
 pub const MAINNET_DEX_DENOM: &str = "uloop";
+// spend records older than this are pruned as new spends come in
+pub const SPEND_HISTORY_MAX_AGE: u64 = 90 * 24 * 60 * 60; // seconds
+
+// bounds the gas a single spend spends on pruning
+pub const SPEND_HISTORY_MAX_PRUNE: usize = 10;
+// the least time admin has to cancel an heir's claim; a longer admin
+// transfer delay applies instead
+pub const INHERITANCE_GRACE_PERIOD: u64 = 7 * 24 * 60 * 60; // seconds
+
+// once executable, a recovery expires if nobody executes it in this long
+pub const RECOVERY_EXECUTION_WINDOW: u64 = 14 * 24 * 60 * 60; // seconds
 
 pub fn get_usdc_sourced_coin(amount: Uint128) -> SourcedCoin {
     SourcedCoin {
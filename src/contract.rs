@@ -1,40 +1,73 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, Event,
-    MessageInfo, Response, StakingMsg, StdError, StdResult, Timestamp, Uint128, WasmMsg,
+    to_binary, Addr, Api, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, Event,
+    MessageInfo, Order, Response, StakingMsg, StdError, StdResult, Storage, Timestamp, Uint128,
+    WasmMsg,
 };
+use cw_storage_plus::Bound;
 
 use cw1::CanExecuteResponse;
 use cw2::{get_contract_version, set_contract_version};
 use semver::Version;
 
-use crate::constants::MAINNET_AXLUSDC_IBC;
+use crate::constants::{
+    INHERITANCE_GRACE_PERIOD, MAINNET_AXLUSDC_IBC, SPEND_HISTORY_MAX_AGE, SPEND_HISTORY_MAX_PRUNE,
+};
+use crate::debug::with_debug_event;
 use crate::error::ContractError;
-use crate::hot_wallet::{HotWallet, HotWalletsResponse};
+use crate::events::{
+    admin_change_event, config_event, fee_debt_event, fee_repay_event, freeze_event,
+    guardian_event, hot_wallet_event, proposal_event, scheduled_transfer_event, session_key_event,
+    signed_execute_event, spend_event, subscription_event,
+};
+use crate::hot_wallet::{HotWallet, HotWalletsResponse, SpendLimitStatusResponse};
 use crate::msg::{
-    AdminResponse, CanSpendResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    AdminResponse, CanSpendResponse, ExecuteMsg, FeeDebtHistoryResponse, FeeDebtResponse,
+    InstantiateMsg, MigrateMsg, MsgSimulation, PendingAdminResponse, QueryMsg, SignersResponse,
+    SimulateExecuteResponse, SpendHistoryResponse,
 };
+use crate::overlay::OverlayStorage;
+use crate::proposal::{Proposal, ProposalResponse, ProposalsResponse};
+use crate::recovery::{
+    GuardianSet, GuardiansResponse, Heir, HeirResponse, Recovery, RecoveryStatusResponse,
+};
+use crate::scheduled::{ScheduledTransfer, ScheduledTransfersResponse};
+use crate::session_key::{SessionKey, SessionKeysResponse};
+use crate::signed::{SignedNonceResponse, SignedPayload};
 use crate::sourced_coin::SourcedCoin;
 use crate::sources::{Sources};
-use crate::state::{State, STATE};
-use crate::submsgs::{PendingSubmsg, SubmsgType, WasmmsgType};
+use crate::state::{
+    AdminTransferWindow, FeeDebtRecord, FeeRepayPolicy, SignerSet, SpendRecord, State,
+    FEE_DEBT_HISTORY, FEE_DEBT_HISTORY_COUNT, GUARDIANS, HEIR, PROPOSALS, PROPOSAL_COUNT, RECOVERY,
+    SCHEDULED_TRANSFERS, SCHEDULED_TRANSFER_COUNT, SESSION_KEYS, SIGNED_NONCES, SIGNERS,
+    SIGNING_KEYS, SPEND_HISTORY, SPEND_HISTORY_COUNT, STATE, SUBSCRIPTIONS, SUBSCRIPTION_COUNT,
+};
+use crate::submsgs::{PendingSubmsg, SubmsgType};
+use crate::subscription::{Subscription, SubscriptionsResponse};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "obi-proxy-contract";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
 struct CorePayload {
     info: MessageInfo,
     this_msg: CosmosMsg,
     current_time: Timestamp,
+    block_height: u64,
+    repay_on_spend: bool,
+    events: Vec<Event>,
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -46,20 +79,69 @@ pub fn instantiate(
     let mut cfg = State {
         admin: valid_admin.clone(),
         pending: valid_admin,
+        admin_transfer_delay: None,
+        admin_transfer_window: None,
+        frozen_by: None,
         hot_wallets: msg.hot_wallets,
         uusd_fee_debt: msg.uusd_fee_debt,
+        max_fee_debt: None,
+        fee_repay_policy: None,
+        proposal_threshold: None,
         fee_lend_repay_wallet: valid_repay_wallet,
         home_network: msg.home_network,
         pair_contracts: vec![],
     };
     cfg.set_pair_contracts(cfg.home_network.clone())?;
     STATE.save(deps.storage, &cfg)?;
+    let signers_event = save_signers(deps.storage, deps.api, cfg.admin.clone(), msg.signers)?;
+    FEE_DEBT_HISTORY_COUNT.save(deps.storage, &0u64)?;
+    if cfg.uusd_fee_debt > Uint128::from(0u128) {
+        record_fee_debt(
+            deps.storage,
+            &env,
+            cfg.fee_lend_repay_wallet,
+            cfg.uusd_fee_debt,
+            "instantiate".to_string(),
+        )?;
+    }
+    Ok(Response::new()
+        .add_event(signers_event)
+        .add_event(admin_change_event(
+            "instantiate",
+            &info.sender,
+            &cfg.admin,
+            &cfg.admin,
+        )))
+}
+
+/// Validates and stores the signers for `admin`, returning the `obisign`
+/// event listing them
+fn save_signers(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    admin: Addr,
+    signers: Vec<String>,
+) -> StdResult<Event> {
     let mut signers_event = Event::new("obisign");
-    for signer in msg.signers {
-        signers_event =
-            signers_event.add_attribute("signer", deps.api.addr_validate(&signer)?.to_string());
+    let mut valid_signers: Vec<Addr> = vec![];
+    for signer in signers {
+        let valid_signer = api.addr_validate(&signer)?;
+        if valid_signers.contains(&valid_signer) {
+            return Err(StdError::GenericErr {
+                msg: format!("Duplicate signer {}", valid_signer),
+            });
+        }
+        signers_event = signers_event.add_attribute("signer", valid_signer.to_string());
+        valid_signers.push(valid_signer);
     }
-    Ok(Response::new().add_event(signers_event))
+    SIGNERS.save(
+        storage,
+        &SignerSet {
+            admin,
+            signers: valid_signers,
+        },
+    )?;
+    Ok(signers_event)
 }
 
 #[allow(unused_variables)]
@@ -80,20 +162,49 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    with_debug_event(|| dispatch_execute(deps, env, info, msg))
+}
+
+fn dispatch_execute(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    match msg {
+    // any admin transaction holds off the heir's dead-man switch, and
+    // cancels the heir's claim if there is one
+    let cancelled_claim = note_admin_activity(deps.storage, &env, &info.sender)?;
+    let res = match msg {
         ExecuteMsg::Execute { msgs } => execute_execute(&mut deps, env, info, msgs, false),
-        ExecuteMsg::SimExecute { msgs } => execute_execute(&mut deps, env, info, msgs, true),
+        ExecuteMsg::SimExecute { msgs } => {
+            // writes go to an overlay that is dropped, so limits and
+            // fee debt are left as they were
+            let mut overlay = OverlayStorage::new(deps.storage);
+            let mut sim_deps = DepsMut {
+                storage: &mut overlay,
+                api: deps.api,
+                querier: deps.querier,
+            };
+            execute_execute(&mut sim_deps, env, info, msgs, true)
+        }
         ExecuteMsg::AddHotWallet { new_hot_wallet } => {
             add_hot_wallet(deps, env, info, new_hot_wallet)
         }
         ExecuteMsg::RmHotWallet { doomed_hot_wallet } => {
             rm_hot_wallet(deps, env, info, doomed_hot_wallet)
         }
+        ExecuteMsg::PauseHotWallet { address } => {
+            set_hot_wallet_paused(deps, env, info, address, true)
+        }
+        ExecuteMsg::UnpauseHotWallet { address } => {
+            set_hot_wallet_paused(deps, env, info, address, false)
+        }
+        ExecuteMsg::RevokeSelf {} => revoke_self(deps, env, info),
         ExecuteMsg::ProposeUpdateAdmin { new_admin } => {
             propose_update_admin(deps, env, info, new_admin)
         }
@@ -101,93 +212,330 @@ pub fn execute(
             confirm_update_admin(deps, env, info, signers)
         }
         ExecuteMsg::CancelUpdateAdmin {} => cancel_update_admin(deps, env, info),
+        ExecuteMsg::RepayFeeDebt { asset } => repay_fee_debt(deps, env, info, asset),
+        ExecuteMsg::AddFeeDebt { amount, memo } => add_fee_debt(deps, env, info, amount, memo),
+        ExecuteMsg::SetMaxFeeDebt { max_fee_debt } => {
+            set_max_fee_debt(deps, env, info, max_fee_debt)
+        }
+        ExecuteMsg::SetFeeRepayPolicy { policy } => set_fee_repay_policy(deps, env, info, policy),
+        ExecuteMsg::SetAdminTransferDelay { delay_seconds } => {
+            set_admin_transfer_delay(deps, env, info, delay_seconds)
+        }
+        ExecuteMsg::SetGuardians {
+            guardians,
+            threshold,
+            recovery_delay,
+        } => set_guardians(deps, env, info, guardians, threshold, recovery_delay),
+        ExecuteMsg::ProposeRecovery { new_admin } => propose_recovery(deps, env, info, new_admin),
+        ExecuteMsg::ApproveRecovery {} => approve_recovery(deps, env, info),
+        ExecuteMsg::ExecuteRecovery {} => execute_recovery(deps, env, info),
+        ExecuteMsg::VetoRecovery {} => veto_recovery(deps, env, info),
+        ExecuteMsg::SetHeir {
+            heir,
+            inactivity_seconds,
+        } => set_heir(deps, env, info, heir, inactivity_seconds),
+        ExecuteMsg::Heartbeat {} => heartbeat(deps, env, info),
+        ExecuteMsg::ClaimInheritance {} => claim_inheritance(deps, env, info),
+        ExecuteMsg::Freeze {} => freeze(deps, env, info),
+        ExecuteMsg::Unfreeze {} => unfreeze(deps, env, info),
+        ExecuteMsg::UpdateSigners { signers } => update_signers(deps, env, info, signers),
+        ExecuteMsg::SetProposalThreshold { threshold } => {
+            set_proposal_threshold(deps, env, info, threshold)
+        }
+        ExecuteMsg::ProposeExecute { msgs, expires } => {
+            propose_execute(deps, env, info, msgs, expires)
+        }
+        ExecuteMsg::ApproveProposal { id } => approve_proposal(deps, env, info, id),
+        ExecuteMsg::ExecuteProposal { id } => execute_proposal(deps, env, info, id),
+        ExecuteMsg::SetSigningKey { pubkey } => set_signing_key(deps, env, info, pubkey),
+        ExecuteMsg::ExecuteSigned {
+            payload,
+            pubkey,
+            signature,
+        } => execute_signed(deps, env, info, payload, pubkey, signature),
+        ExecuteMsg::CreateSessionKey { session_key } => {
+            create_session_key(deps, env, info, session_key)
+        }
+        ExecuteMsg::RevokeSessionKey { address } => revoke_session_key(deps, env, info, address),
+        ExecuteMsg::AddSubscription { subscription } => {
+            add_subscription(deps, env, info, subscription)
+        }
+        ExecuteMsg::CancelSubscription { id } => cancel_subscription(deps, env, info, id),
+        ExecuteMsg::TriggerPayments { ids } => trigger_payments(deps, env, info, ids),
+        ExecuteMsg::ScheduleTransfer { msg, execute_after } => {
+            schedule_transfer(deps, env, info, msg, execute_after)
+        }
+        ExecuteMsg::CancelScheduledTransfer { id } => {
+            cancel_scheduled_transfer(deps, env, info, id)
+        }
+        ExecuteMsg::ExecuteScheduledTransfer { id } => {
+            execute_scheduled_transfer(deps, env, info, id)
+        }
+    }?;
+    match cancelled_claim {
+        Some(event) => Ok(res.add_event(event)),
+        None => Ok(res),
     }
 }
 
-// Simulation gatekeeping is all in this block
+/// What a run of execute_execute checked, for the SimulateExecute query.
+/// Only messages that went through the spend checks get a verdict.
+/// With `keep_going`, a rejected message gets its error as a verdict and
+/// its writes are dropped, so the rest of the batch is still checked.
+#[derive(Default)]
+struct ExecuteTrace {
+    keep_going: bool,
+    fee_repay: Option<SourcedCoin>,
+    msgs: Vec<MsgSimulation>,
+}
+
 pub fn execute_execute(
     deps: &mut DepsMut,
     env: Env,
     info: MessageInfo,
     msgs: Vec<CosmosMsg>,
     simulation: bool,
+) -> Result<Response, ContractError> {
+    traced_execute(
+        deps,
+        env,
+        info,
+        msgs,
+        simulation,
+        &mut ExecuteTrace::default(),
+    )
+}
+
+// Simulation gatekeeping is all in this block
+fn traced_execute(
+    deps: &mut DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<CosmosMsg>,
+    simulation: bool,
+    trace: &mut ExecuteTrace,
 ) -> Result<Response, ContractError> {
     let cfg = STATE.load(deps.storage)?;
+    let is_admin = cfg.is_admin(info.sender.to_string());
+    if cfg.is_frozen() && !is_admin {
+        return Err(ContractError::Frozen {});
+    }
     let mut res = Response::new();
-    if cfg.uusd_fee_debt == Uint128::from(0u128) && cfg.is_admin(info.sender.to_string()) {
-        // if there is no debt AND user is admin, process immediately
+    // the fee repay policy decides whether debt is repaid out of the first
+    // spend, up front from the home asset, or not yet at all
+    let mut repay_on_spend = false;
+    if cfg.uusd_fee_debt > Uint128::from(0u128) {
+        let repay_up_front = match cfg.get_fee_repay_policy() {
+            FeeRepayPolicy::RepayFirstSpend => {
+                repay_on_spend = true;
+                false
+            }
+            FeeRepayPolicy::RepayOnAnyTx => true,
+            FeeRepayPolicy::RepayByDeadline { deadline } => {
+                if env.block.time < deadline {
+                    false
+                } else if is_admin {
+                    true
+                } else {
+                    return Err(ContractError::FeeRepayDeadlinePassed {});
+                }
+            }
+        };
+        if repay_up_front {
+            let home_denom = cfg.get_home_denom()?;
+            let sourced_repay = try_repay_debt(deps, home_denom)?;
+            trace.fee_repay = sourced_repay.to_sourced_coin();
+            if !simulation {
+                res = res.add_event(sourced_repay.to_event(&info.sender));
+                if let Some(repay_msg) = sourced_repay.repay_msg {
+                    res = res.add_message(repay_msg);
+                }
+            }
+        }
+    }
+    if !repay_on_spend && is_admin {
+        // if there is no debt to repay on spend AND user is admin, process immediately
         res = res.add_attribute("action", "execute_execute");
         if !simulation {
             res = res.add_messages(msgs);
         }
     } else {
+        let mut core_payload = CorePayload {
+            info,
+            this_msg: CosmosMsg::Custom(Empty {}),
+            current_time: env.block.time,
+            block_height: env.block.height,
+            repay_on_spend,
+            events: vec![],
+        };
+        let sender = core_payload.info.sender.clone();
+        // session keys are limited to their own contract, so they never
+        // reach the authorized contract shortcut below
+        if let Some(session_key) = SESSION_KEYS.may_load(deps.storage, &sender)? {
+            return execute_session_key(
+                deps,
+                &core_payload,
+                session_key,
+                msgs,
+                simulation,
+                trace,
+                res,
+            );
+        }
+        // transaction limits hold for authorized contracts too
+        cfg.check_tx_limits(env.block.time, sender.to_string())?;
         // certain authorized token contracts process immediately if hot wallet (or admin)
-        if let CosmosMsg::Wasm(WasmMsg::Execute {
+        if let Some(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr,
             msg: _,
             funds,
-        }) = msgs[0].clone()
+        })) = msgs.first()
         {
             if funds.is_empty()
-                && cfg.is_authorized_hotwallet_contract(contract_addr)
-                && cfg.is_active_hot_wallet(info.sender.clone())?
+                && cfg.is_authorized_hotwallet_contract(contract_addr.clone())
+                && cfg.is_active_hot_wallet(sender.clone())?
             {
-                let res = Response::new()
-                    .add_attribute("action", "execute_authorized_contract")
-                    .add_message(msgs[0].clone());
+                let mut res = res.add_attribute("action", "execute_authorized_contract");
+                if !simulation {
+                    res = res.add_message(msgs[0].clone());
+                }
+                let mut cfg = cfg;
+                cfg.record_tx(env.block.time, sender.to_string())?;
+                STATE.save(deps.storage, &cfg)?;
                 return Ok(res);
             }
         }
         // otherwise, we need to do some checking. Note that attaching
         // fee repayment is handled in the try_bank_send and (todo)
         // the try_wasm_send functions
-        let mut core_payload = CorePayload {
-            info,
-            this_msg: CosmosMsg::Custom(Empty {}),
-            current_time: env.block.time,
-        };
         for this_msg in msgs {
             core_payload.this_msg = this_msg.clone();
-            let maybe_repay_msg =
-                check_and_spend_total_coins(deps, this_msg.clone(), &mut core_payload)?;
+            // kept even if the message is rejected, so the error can be
+            // put against it
+            let mut verdict = unsimulated_msg(this_msg.clone(), None);
+            let checked = if trace.keep_going {
+                let mut msg_overlay = OverlayStorage::new(&*deps.storage);
+                let checked = check_and_spend_total_coins(
+                    &mut DepsMut {
+                        storage: &mut msg_overlay,
+                        api: deps.api,
+                        querier: deps.querier,
+                    },
+                    this_msg.clone(),
+                    &mut core_payload,
+                    &mut verdict,
+                );
+                let writes = msg_overlay.into_writes();
+                if let Err(e) = checked {
+                    verdict.error = Some(e.to_string());
+                    trace.msgs.push(verdict);
+                    continue;
+                }
+                writes.apply_to(deps.storage);
+                checked
+            } else {
+                check_and_spend_total_coins(deps, this_msg.clone(), &mut core_payload, &mut verdict)
+            };
+            trace.msgs.push(verdict);
+            let maybe_repay_msg = checked?;
             if !simulation {
                 if let Some(msg) = maybe_repay_msg {
+                    res = res.add_event(msg.to_event(&core_payload.info.sender));
                     if let Some(repay_msg) = msg.repay_msg {
                         res = res.add_message(repay_msg);
                     }
-                    res = res.add_attributes(msg.wrapped_sources.to_attributes())
                 }
-                res = res.add_message(this_msg);
+                res = res
+                    .add_events(core_payload.events.drain(..))
+                    .add_message(this_msg);
             }
             res = res.add_attribute("action", "execute_spend_limit_or_debt");
         }
+        // the whole batch counts as one transaction
+        let mut cfg = STATE.load(deps.storage)?;
+        cfg.record_tx(env.block.time, core_payload.info.sender.to_string())?;
+        STATE.save(deps.storage, &cfg)?;
     }
     Ok(res)
 }
 
+fn execute_session_key(
+    deps: &mut DepsMut,
+    core_payload: &CorePayload,
+    mut session_key: SessionKey,
+    msgs: Vec<CosmosMsg>,
+    simulation: bool,
+    trace: &mut ExecuteTrace,
+    mut res: Response,
+) -> Result<Response, ContractError> {
+    let sender = &core_payload.info.sender;
+    if session_key.is_expired(core_payload.current_time) {
+        return Err(ContractError::SessionKeyExpired {});
+    }
+    let mut spend: Vec<Coin> = vec![];
+    let mut usd_spend = Uint128::from(0u128);
+    for msg in msgs.iter() {
+        for coin in session_key.check_msg(msg)? {
+            spend.push(coin.clone());
+            let unconverted_coin = SourcedCoin {
+                coin,
+                wrapped_sources: Sources { sources: vec![] },
+            };
+            let converted_coin = unconverted_coin.get_converted_to_usdc(deps.as_ref(), false)?;
+            usd_spend = usd_spend.saturating_add(converted_coin.coin.amount);
+        }
+    }
+    session_key.spend(usd_spend)?;
+    SESSION_KEYS.save(deps.storage, sender, &session_key)?;
+    // as with hot wallets, fee debt is repaid before the first funds go out
+    if core_payload.repay_on_spend && !spend.is_empty() {
+        let sourced_repay = spend
+            .iter()
+            .find_map(|coin| try_repay_debt(deps, coin.denom.clone()).ok());
+        let cfg = STATE.load(deps.storage)?;
+        if cfg.uusd_fee_debt > Uint128::from(0u128) {
+            return Err(ContractError::UnableToRepayDebt(
+                cfg.uusd_fee_debt.to_string(),
+            ));
+        }
+        if let Some(sourced_repay) = sourced_repay {
+            trace.fee_repay = sourced_repay.to_sourced_coin();
+            if !simulation {
+                res = res.add_event(sourced_repay.to_event(sender));
+                if let Some(repay_msg) = sourced_repay.repay_msg {
+                    res = res.add_message(repay_msg);
+                }
+            }
+        }
+    }
+    let res = res.add_attribute("action", "execute_session_key");
+    if simulation {
+        return Ok(res);
+    }
+    Ok(res
+        .add_event(session_key_event(
+            "execute_session_key",
+            sender,
+            sender.as_str(),
+            Some(usd_spend),
+        ))
+        .add_messages(msgs))
+}
+
 fn check_and_spend_total_coins(
     deps: &mut DepsMut,
     msg: CosmosMsg,
     core_payload: &mut CorePayload,
+    verdict: &mut MsgSimulation,
 ) -> Result<Option<SourcedRepayMsg>, ContractError> {
-    let mut processed_msg = PendingSubmsg {
-        msg,
-        contract_addr: None,
-        binarymsg: None,
-        funds: vec![],
-        ty: SubmsgType::Unknown,
-    };
+    let mut processed_msg = PendingSubmsg::new(msg);
     processed_msg.add_funds(core_payload.info.funds.to_vec());
     match processed_msg.process_and_get_msg_type() {
-        SubmsgType::BankSend
-        | SubmsgType::BankBurn
-        | SubmsgType::ExecuteWasm(WasmmsgType::Cw20Transfer)
-        | SubmsgType::ExecuteWasm(WasmmsgType::Cw20Send)
-        | SubmsgType::ExecuteWasm(WasmmsgType::Cw20Burn)
-        | SubmsgType::ExecuteWasm(WasmmsgType::Cw20IncreaseAllowance) => {
-            check_coins(deps, core_payload, processed_msg.funds)
-        }
-        SubmsgType::ExecuteWasm(_other_type) => {
+        ty if ty.is_spend() => check_coins(deps, core_payload, processed_msg.funds, verdict),
+        SubmsgType::Unknown => {
+            Err(ContractError::BadMessageType("unknown".to_string()))
+        }
+        _other_type => {
             let cfg = STATE.load(deps.storage)?;
             if cfg.is_admin(core_payload.info.sender.to_string()) {
                 Ok(None)
@@ -195,23 +543,48 @@ fn check_and_spend_total_coins(
                 Err(ContractError::OnlyTransferSendAllowed {})
             }
         }
-        SubmsgType::Unknown => {
-            Err(ContractError::BadMessageType("unknown".to_string()))
-        }
     }
 }
 
 pub struct SourcedRepayMsg {
     pub repay_msg: Option<BankMsg>,
+    pub usd_value: Uint128,
     pub wrapped_sources: Sources,
 }
 
+impl SourcedRepayMsg {
+    pub fn to_event(&self, wallet: &Addr) -> Event {
+        let (repay_to, repaid) = match &self.repay_msg {
+            Some(BankMsg::Send { to_address, amount }) => (to_address.clone(), amount.clone()),
+            _ => (String::new(), vec![]),
+        };
+        fee_repay_event(
+            wallet,
+            &repay_to,
+            self.usd_value,
+            &repaid,
+            &self.wrapped_sources,
+        )
+    }
+
+    /// the asset repaid, priced as SimulateExecute shows it
+    pub fn to_sourced_coin(&self) -> Option<SourcedCoin> {
+        match &self.repay_msg {
+            Some(BankMsg::Send { amount, .. }) => amount.first().map(|coin| SourcedCoin {
+                coin: coin.clone(),
+                wrapped_sources: self.wrapped_sources.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
 fn convert_debt_to_asset_spent(
     deps: Deps,
     usd_debt: Uint128,
-    asset: Coin,
+    denom: String,
 ) -> Result<SourcedCoin, ContractError> {
-    match asset.denom.as_str() {
+    match denom.as_str() {
         val if val == MAINNET_AXLUSDC_IBC => Ok(SourcedCoin {
             coin: Coin {
                 denom: MAINNET_AXLUSDC_IBC.to_string(),
@@ -222,7 +595,7 @@ fn convert_debt_to_asset_spent(
         "ujuno" | "ujunox" | "testtokens" => {
             let unconverted_fee = SourcedCoin {
                 coin: Coin {
-                    denom: asset.denom.clone(),
+                    denom: denom.clone(),
                     amount: usd_debt,
                 },
                 wrapped_sources: Sources { sources: vec![] },
@@ -233,9 +606,9 @@ fn convert_debt_to_asset_spent(
     }
 }
 
-fn try_repay_debt(deps: &mut DepsMut, asset: Coin) -> Result<SourcedRepayMsg, ContractError> {
+fn try_repay_debt(deps: &mut DepsMut, denom: String) -> Result<SourcedRepayMsg, ContractError> {
     let cfg: State = STATE.load(deps.storage)?;
-    let swaps = convert_debt_to_asset_spent(deps.as_ref(), cfg.uusd_fee_debt, asset)?;
+    let swaps = convert_debt_to_asset_spent(deps.as_ref(), cfg.uusd_fee_debt, denom)?;
     let mut new_cfg = cfg.clone();
     new_cfg.uusd_fee_debt = Uint128::from(0u128);
     STATE.save(deps.storage, &new_cfg)?;
@@ -244,6 +617,7 @@ fn try_repay_debt(deps: &mut DepsMut, asset: Coin) -> Result<SourcedRepayMsg, Co
             to_address: cfg.fee_lend_repay_wallet.to_string(),
             amount: vec![swaps.coin.clone()],
         }),
+        usd_value: cfg.uusd_fee_debt,
         wrapped_sources: swaps.wrapped_sources,
     })
 }
@@ -252,13 +626,15 @@ fn check_coins(
     deps: &mut DepsMut,
     core_payload: &mut CorePayload,
     spend: Vec<Coin>,
+    verdict: &mut MsgSimulation,
 ) -> Result<Option<SourcedRepayMsg>, ContractError> {
     let cfg = STATE.load(deps.storage)?;
     let mut updated_cfg: State;
     let mut sourced_repay: Option<SourcedRepayMsg> = None;
-    if cfg.uusd_fee_debt > Uint128::from(0u128) {
+    if core_payload.repay_on_spend && cfg.uusd_fee_debt > Uint128::from(0u128) {
         'debt_cycle: for coin in spend.clone() {
-            if let Ok(msg) = try_repay_debt(deps, coin) {
+            if let Ok(msg) = try_repay_debt(deps, coin.denom) {
+                verdict.fee_repay = msg.to_sourced_coin();
                 sourced_repay = Some(msg);
                 break 'debt_cycle;
             }
@@ -272,16 +648,211 @@ fn check_coins(
     } else {
         updated_cfg = cfg;
     }
-    updated_cfg.check_and_update_spend_limits(
+    let usd_spend = updated_cfg.check_and_update_spend_limits(
         deps.as_ref(),
         core_payload.current_time,
         core_payload.info.sender.to_string(),
-        spend,
+        spend.clone(),
     )?;
     STATE.save(deps.storage, &updated_cfg)?;
+    let sender = core_payload.info.sender.clone();
+    if !updated_cfg.is_admin(sender.to_string()) {
+        verdict.usd_value = Some(usd_spend.clone());
+        let record = record_spend(deps.storage, core_payload, spend, usd_spend)?;
+        let limit_remaining = updated_cfg
+            .maybe_get_hot_wallet(sender.to_string())?
+            .spend_limits
+            .first()
+            .map(|limit| limit.limit_remaining);
+        verdict.limit_remaining = limit_remaining;
+        core_payload
+            .events
+            .push(spend_event(&sender, &record, limit_remaining));
+    }
     Ok(sourced_repay)
 }
 
+fn summarize_msg(msg: &CosmosMsg) -> String {
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, .. }) => format!("bank send to {}", to_address),
+        CosmosMsg::Bank(BankMsg::Burn { .. }) => "bank burn".to_string(),
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            format!("execute on {}", contract_addr)
+        }
+        _ => "other".to_string(),
+    }
+}
+
+fn record_spend(
+    storage: &mut dyn Storage,
+    core_payload: &CorePayload,
+    coins: Vec<Coin>,
+    usd_spend: SourcedCoin,
+) -> StdResult<SpendRecord> {
+    let wallet = core_payload.info.sender.clone();
+    // prune this wallet's oldest records, a few at a time
+    let cutoff = core_payload
+        .current_time
+        .seconds()
+        .saturating_sub(SPEND_HISTORY_MAX_AGE);
+    let mut expired_ids: Vec<u64> = vec![];
+    for item in SPEND_HISTORY
+        .prefix(wallet.clone())
+        .range(storage, None, None, Order::Ascending)
+        .take(SPEND_HISTORY_MAX_PRUNE)
+    {
+        let (id, record) = item?;
+        if record.time.seconds() >= cutoff {
+            break;
+        }
+        expired_ids.push(id);
+    }
+    for id in expired_ids {
+        SPEND_HISTORY.remove(storage, (wallet.clone(), id));
+    }
+
+    let id = SPEND_HISTORY_COUNT.may_load(storage)?.unwrap_or(0u64);
+    let record = SpendRecord {
+        time: core_payload.current_time,
+        block_height: core_payload.block_height,
+        msg_summary: summarize_msg(&core_payload.this_msg),
+        coins,
+        usd_amount: usd_spend.coin.amount,
+        sources: usd_spend.wrapped_sources,
+    };
+    SPEND_HISTORY.save(storage, (wallet, id), &record)?;
+    SPEND_HISTORY_COUNT.save(storage, &(id + 1))?;
+    Ok(record)
+}
+
+pub fn repay_fee_debt(
+    mut deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    asset: String,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) && !cfg.is_active_hot_wallet(info.sender.clone())? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if cfg.uusd_fee_debt == Uint128::from(0u128) {
+        return Err(ContractError::NoFeeDebt {});
+    }
+    // repayment is not a spend, so spend limits are left untouched
+    let sourced_repay = try_repay_debt(&mut deps, asset)?;
+    let mut res = Response::new()
+        .add_attribute("action", "repay_fee_debt")
+        .add_event(sourced_repay.to_event(&info.sender));
+    if let Some(repay_msg) = sourced_repay.repay_msg {
+        res = res.add_message(repay_msg);
+    }
+    Ok(res)
+}
+
+fn record_fee_debt(
+    storage: &mut dyn Storage,
+    env: &Env,
+    lender: Addr,
+    amount: Uint128,
+    memo: String,
+) -> StdResult<()> {
+    // older instances have no count stored yet
+    let id = FEE_DEBT_HISTORY_COUNT.may_load(storage)?.unwrap_or(0u64);
+    FEE_DEBT_HISTORY.save(
+        storage,
+        id,
+        &FeeDebtRecord {
+            lender,
+            amount,
+            memo,
+            time: env.block.time,
+            block_height: env.block.height,
+        },
+    )?;
+    FEE_DEBT_HISTORY_COUNT.save(storage, &(id + 1))
+}
+
+/// adds to the fee debt on behalf of the fee lender, up to the maximum
+fn lend_fee_debt(
+    storage: &mut dyn Storage,
+    env: &Env,
+    lender: &Addr,
+    amount: Uint128,
+    memo: String,
+) -> Result<(), ContractError> {
+    let mut cfg = STATE.load(storage)?;
+    if !cfg.is_fee_lender(lender.to_string()) {
+        return Err(ContractError::CallerIsNotFeeLender {});
+    }
+    let new_debt = cfg.uusd_fee_debt.checked_add(amount)?;
+    let max_fee_debt = cfg.max_fee_debt.unwrap_or_default();
+    if new_debt > max_fee_debt {
+        return Err(ContractError::FeeDebtOverMaximum(
+            new_debt.to_string(),
+            max_fee_debt.to_string(),
+        ));
+    }
+    cfg.uusd_fee_debt = new_debt;
+    STATE.save(storage, &cfg)?;
+    record_fee_debt(storage, env, lender.clone(), amount, memo)?;
+    Ok(())
+}
+
+pub fn add_fee_debt(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    memo: String,
+) -> Result<Response, ContractError> {
+    lend_fee_debt(deps.storage, &env, &info.sender, amount, memo.clone())?;
+    Ok(Response::new()
+        .add_attribute("action", "add_fee_debt")
+        .add_attribute("amount", amount)
+        .add_attribute("memo", memo)
+        .add_event(fee_debt_event("add_fee_debt", &info.sender, Some(amount))))
+}
+
+pub fn set_max_fee_debt(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    max_fee_debt: Uint128,
+) -> Result<Response, ContractError> {
+    let mut cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        Err(ContractError::Unauthorized {})
+    } else {
+        cfg.max_fee_debt = Some(max_fee_debt);
+        STATE.save(deps.storage, &cfg)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_max_fee_debt")
+            .add_event(fee_debt_event(
+                "set_max_fee_debt",
+                &info.sender,
+                Some(max_fee_debt),
+            )))
+    }
+}
+
+pub fn set_fee_repay_policy(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    policy: FeeRepayPolicy,
+) -> Result<Response, ContractError> {
+    let mut cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        Err(ContractError::Unauthorized {})
+    } else {
+        cfg.fee_repay_policy = Some(policy);
+        STATE.save(deps.storage, &cfg)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_fee_repay_policy")
+            .add_event(fee_debt_event("set_fee_repay_policy", &info.sender, None)))
+    }
+}
+
 pub fn add_hot_wallet(
     deps: DepsMut,
     _env: Env,
@@ -298,11 +869,79 @@ pub fn add_hot_wallet(
     {
         Err(ContractError::HotWalletExists {})
     } else {
-        let _addrcheck = deps.api.addr_validate(&new_hot_wallet.address)?;
+        let addrcheck = deps.api.addr_validate(&new_hot_wallet.address)?;
+        if SESSION_KEYS.has(deps.storage, &addrcheck) {
+            return Err(ContractError::SessionKeyExists {});
+        }
+        let event = hot_wallet_event("add_hot_wallet", &info.sender, &new_hot_wallet.address);
         cfg.add_hot_wallet(new_hot_wallet);
         STATE.save(deps.storage, &cfg)?;
-        Ok(Response::new().add_attribute("action", "add_hot_wallet"))
+        Ok(Response::new()
+            .add_attribute("action", "add_hot_wallet")
+            .add_event(event))
+    }
+}
+
+pub fn create_session_key(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    session_key: SessionKey,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let address = deps.api.addr_validate(&session_key.address)?;
+    deps.api.addr_validate(&session_key.contract)?;
+    session_key.assert_is_valid(env.block.time)?;
+    if cfg.is_admin(address.to_string())
+        || cfg.maybe_get_hot_wallet(address.to_string()).is_ok()
+        || SESSION_KEYS.has(deps.storage, &address)
+    {
+        return Err(ContractError::SessionKeyExists {});
+    }
+    SESSION_KEYS.save(
+        deps.storage,
+        &address,
+        &SessionKey {
+            usd_spent: 0,
+            ..session_key
+        },
+    )?;
+    Ok(Response::new()
+        .add_attribute("action", "create_session_key")
+        .add_event(session_key_event(
+            "create_session_key",
+            &info.sender,
+            address.as_str(),
+            None,
+        )))
+}
+
+pub fn revoke_session_key(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) && info.sender != address {
+        return Err(ContractError::Unauthorized {});
+    }
+    let address = deps.api.addr_validate(&address)?;
+    if !SESSION_KEYS.has(deps.storage, &address) {
+        return Err(ContractError::SessionKeyDoesNotExist {});
     }
+    SESSION_KEYS.remove(deps.storage, &address);
+    Ok(Response::new()
+        .add_attribute("action", "revoke_session_key")
+        .add_event(session_key_event(
+            "revoke_session_key",
+            &info.sender,
+            address.as_str(),
+            None,
+        )))
 }
 
 pub fn rm_hot_wallet(
@@ -321,16 +960,58 @@ pub fn rm_hot_wallet(
     {
         Err(ContractError::HotWalletDoesNotExist {})
     } else {
+        let event = hot_wallet_event("rm_hot_wallet", &info.sender, &doomed_hot_wallet);
+        SIGNING_KEYS.remove(deps.storage, &deps.api.addr_validate(&doomed_hot_wallet)?);
         cfg.rm_hot_wallet(doomed_hot_wallet);
         STATE.save(deps.storage, &cfg)?;
-        Ok(Response::new().add_attribute("action", "rm_hot_wallet"))
+        Ok(Response::new()
+            .add_attribute("action", "rm_hot_wallet")
+            .add_event(event))
     }
 }
 
-pub fn propose_update_admin(
+pub fn set_hot_wallet_paused(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
+    address: String,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let mut cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    cfg.set_hot_wallet_paused(address.clone(), paused)?;
+    STATE.save(deps.storage, &cfg)?;
+    let action = if paused {
+        "pause_hot_wallet"
+    } else {
+        "unpause_hot_wallet"
+    };
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("hot_wallet", address.clone())
+        .add_event(hot_wallet_event(action, &info.sender, &address)))
+}
+
+pub fn revoke_self(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut cfg = STATE.load(deps.storage)?;
+    cfg.set_hot_wallet_paused(info.sender.to_string(), true)?;
+    STATE.save(deps.storage, &cfg)?;
+    Ok(Response::new()
+        .add_attribute("action", "revoke_self")
+        .add_attribute("hot_wallet", info.sender.clone())
+        .add_event(hot_wallet_event(
+            "revoke_self",
+            &info.sender,
+            info.sender.as_str(),
+        )))
+}
+
+pub fn propose_update_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     new_admin: String,
 ) -> Result<Response, ContractError> {
     let mut cfg = STATE.load(deps.storage)?;
@@ -338,29 +1019,65 @@ pub fn propose_update_admin(
         Err(ContractError::Unauthorized {})
     } else {
         cfg.pending = deps.api.addr_validate(&new_admin)?;
+        // delay is fixed at proposal time, so later changes to it
+        // don't affect a proposal already in its window
+        cfg.admin_transfer_window = Some(AdminTransferWindow {
+            proposed_at: env.block.time,
+            executable_at: env
+                .block
+                .time
+                .plus_seconds(cfg.admin_transfer_delay.unwrap_or(0)),
+        });
         STATE.save(deps.storage, &cfg)?;
 
-        let res = Response::new().add_attribute("action", "propose_update_admin");
+        let res = Response::new()
+            .add_attribute("action", "propose_update_admin")
+            .add_event(admin_change_event(
+                "propose_update_admin",
+                &info.sender,
+                &cfg.admin,
+                &cfg.pending,
+            ));
         Ok(res)
     }
 }
 
 pub fn confirm_update_admin(
-    deps: DepsMut,
+    mut deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     signers: Vec<String>,
 ) -> Result<Response, ContractError> {
-    let mut signers_event = Event::new("obisign");
-    for signer in signers {
-        signers_event =
-            signers_event.add_attribute("signer", deps.api.addr_validate(&signer)?.to_string());
-    }
-    let mut res = execute_update_admin(deps, _env, info, false)?;
+    let mut res = execute_update_admin(deps.branch(), _env, info, false)?;
+    // the new admin's signers replace the old admin's
+    let cfg = STATE.load(deps.storage)?;
+    let signers_event = save_signers(deps.storage, deps.api, cfg.admin, signers)?;
     res = res.add_event(signers_event);
     Ok(res)
 }
 
+pub fn update_signers(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    signers: Vec<String>,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let signers_event = save_signers(deps.storage, deps.api, cfg.admin, signers)?;
+    let signer_count = signers_event.attributes.len();
+    Ok(Response::new()
+        .add_attribute("action", "update_signers")
+        .add_event(signers_event)
+        .add_event(config_event(
+            "update_signers",
+            &info.sender,
+            signer_count.to_string(),
+        )))
+}
+
 pub fn cancel_update_admin(
     deps: DepsMut,
     _env: Env,
@@ -371,41 +1088,873 @@ pub fn cancel_update_admin(
 
 fn execute_update_admin(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     cancel: bool,
 ) -> Result<Response, ContractError> {
     let mut cfg = STATE.load(deps.storage)?;
-    if !cfg.is_pending(info.sender.to_string()) {
-        Err(ContractError::CallerIsNotPendingNewAdmin {})
-    } else {
-        match cancel {
-            true => cfg.pending = cfg.admin.clone(),
-            false => cfg.admin = cfg.pending.clone(),
-        };
-        STATE.save(deps.storage, &cfg)?;
-
-        let res = Response::new().add_attribute("action", "confirm_update_admin");
-        Ok(res)
-    }
-}
+    let old_admin = cfg.admin.clone();
+    let new_admin = cfg.pending.clone();
+    let action = match cancel {
+        true => {
+            if !cfg.is_admin(info.sender.to_string()) && !cfg.is_pending(info.sender.to_string()) {
+                return Err(ContractError::Unauthorized {});
+            }
+            cfg.pending = cfg.admin.clone();
+            "cancel_update_admin"
+        }
+        false => {
+            if !cfg.is_pending(info.sender.to_string()) {
+                return Err(ContractError::CallerIsNotPendingNewAdmin {});
+            }
+            if let Some(window) = cfg.admin_transfer_window.clone() {
+                if env.block.time < window.executable_at {
+                    return Err(ContractError::AdminTransferTimelocked(
+                        window.executable_at.seconds(),
+                    ));
+                }
+            }
+            cfg.admin = cfg.pending.clone();
+            // the heir was chosen by the old admin
+            HEIR.remove(deps.storage);
+            "confirm_update_admin"
+        }
+    };
+    cfg.admin_transfer_window = None;
+    STATE.save(deps.storage, &cfg)?;
 
-fn can_execute(deps: Deps, sender: &str) -> StdResult<bool> {
-    let cfg = STATE.load(deps.storage)?;
-    let can = cfg.is_admin(sender.to_string());
-    Ok(can)
+    let res = Response::new()
+        .add_attribute("action", action)
+        .add_event(admin_change_event(
+            action,
+            &info.sender,
+            &old_admin,
+            &new_admin,
+        ));
+    Ok(res)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
+pub fn set_admin_transfer_delay(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    delay_seconds: u64,
+) -> Result<Response, ContractError> {
+    let mut cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        Err(ContractError::Unauthorized {})
+    } else {
+        cfg.admin_transfer_delay = Some(delay_seconds);
+        STATE.save(deps.storage, &cfg)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_admin_transfer_delay")
+            .add_event(config_event(
+                "set_admin_transfer_delay",
+                &info.sender,
+                delay_seconds.to_string(),
+            )))
+    }
+}
+
+pub fn set_proposal_threshold(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    let mut cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        Err(ContractError::Unauthorized {})
+    } else if threshold < 2 {
+        // the proposer approves on creation, so 1 would let a single
+        // hot wallet spend with admin's authority
+        Err(ContractError::InvalidProposalThreshold {})
+    } else {
+        cfg.proposal_threshold = Some(threshold);
+        STATE.save(deps.storage, &cfg)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_proposal_threshold")
+            .add_event(config_event(
+                "set_proposal_threshold",
+                &info.sender,
+                threshold.to_string(),
+            )))
+    }
+}
+
+/// returns true if the address is an active hot wallet or one of the
+/// current admin's signers
+fn is_approver(deps: Deps, cfg: &State, addr: &Addr) -> StdResult<bool> {
+    if cfg.is_active_hot_wallet(addr.clone())? {
+        return Ok(true);
+    }
+    Ok(match SIGNERS.may_load(deps.storage)? {
+        Some(signer_set) if signer_set.admin == cfg.admin => signer_set.signers.contains(addr),
+        _ => false,
+    })
+}
+
+/// the approvals that still count, leaving out approvers removed, paused
+/// or rotated out since they approved
+fn current_approvals(deps: Deps, cfg: &State, proposal: &Proposal) -> StdResult<Vec<Addr>> {
+    let mut approvals: Vec<Addr> = vec![];
+    for approver in proposal.approvals.iter() {
+        if is_approver(deps, cfg, approver)? {
+            approvals.push(approver.clone());
+        }
+    }
+    Ok(approvals)
+}
+
+pub fn propose_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<CosmosMsg>,
+    expires: Timestamp,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if cfg.proposal_threshold.is_none() {
+        return Err(ContractError::ProposalsNotEnabled {});
+    }
+    if !is_approver(deps.as_ref(), &cfg, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if msgs.is_empty() {
+        return Err(ContractError::UninitializedMessage {});
+    }
+    if env.block.time >= expires {
+        return Err(ContractError::ProposalExpired {});
+    }
+    // same classification as hot wallet spends in execute_execute
+    let mut msg_types: Vec<SubmsgType> = vec![];
+    let mut spend: Vec<Coin> = vec![];
+    for msg in msgs.clone() {
+        let mut processed_msg = PendingSubmsg::new(msg);
+        match processed_msg.process_and_get_msg_type() {
+            ty if ty.is_spend() => {
+                spend.extend(processed_msg.funds);
+                msg_types.push(ty);
+            }
+            SubmsgType::Unknown => {
+                return Err(ContractError::BadMessageType("unknown".to_string()))
+            }
+            _other_type => return Err(ContractError::OnlyTransferSendAllowed {}),
+        }
+    }
+    let id = PROPOSAL_COUNT.may_load(deps.storage)?.unwrap_or(0u64);
+    PROPOSALS.save(
+        deps.storage,
+        id,
+        &Proposal {
+            proposer: info.sender.clone(),
+            msgs,
+            msg_types,
+            spend,
+            approvals: vec![info.sender.clone()],
+            expires,
+            executed: false,
+        },
+    )?;
+    PROPOSAL_COUNT.save(deps.storage, &(id + 1))?;
+    Ok(Response::new()
+        .add_attribute("action", "propose_execute")
+        .add_attribute("proposal_id", id.to_string())
+        .add_event(proposal_event("propose_execute", &info.sender, id)))
+}
+
+pub fn approve_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !is_approver(deps.as_ref(), &cfg, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut proposal = PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ProposalDoesNotExist {})?;
+    proposal.assert_is_open(env.block.time)?;
+    proposal.approve(info.sender.clone());
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+    Ok(Response::new()
+        .add_attribute("action", "approve_proposal")
+        .add_attribute("proposal_id", id.to_string())
+        .add_event(proposal_event("approve_proposal", &info.sender, id)))
+}
+
+pub fn execute_proposal(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    let mut proposal = PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ProposalDoesNotExist {})?;
+    if cfg.is_frozen() {
+        return Err(ContractError::Frozen {});
+    }
+    let threshold = cfg
+        .proposal_threshold
+        .ok_or(ContractError::ProposalsNotEnabled {})?;
+    proposal.approvals = current_approvals(deps.as_ref(), &cfg, &proposal)?;
+    proposal.assert_is_executable(threshold, env.block.time)?;
+    proposal.executed = true;
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+    // approved proposals go out with the admin's authority, so spend
+    // limits don't apply but fee repayment still does
+    let admin_info = MessageInfo {
+        sender: cfg.admin,
+        funds: vec![],
+    };
+    let res = execute_execute(&mut deps, env, admin_info, proposal.msgs, false)?;
+    Ok(res
+        .add_attribute("action", "execute_proposal")
+        .add_attribute("proposal_id", id.to_string())
+        .add_event(proposal_event("execute_proposal", &info.sender, id)))
+}
+
+pub fn set_signing_key(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_active_hot_wallet(info.sender.clone())? {
+        return Err(ContractError::Unauthorized {});
+    }
+    // compressed (33 bytes) or uncompressed (65 bytes) secp256k1 keys
+    if pubkey.len() != 33 && pubkey.len() != 65 {
+        return Err(ContractError::InvalidSigningKey {});
+    }
+    SIGNING_KEYS.save(deps.storage, &info.sender, &pubkey)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_signing_key")
+        .add_event(hot_wallet_event(
+            "set_signing_key",
+            &info.sender,
+            info.sender.as_str(),
+        )))
+}
+
+pub fn execute_signed(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    payload: SignedPayload,
+    pubkey: Binary,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    let wallet = deps.api.addr_validate(&payload.wallet)?;
+    if !cfg.is_active_hot_wallet(wallet.clone())? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let signing_key = SIGNING_KEYS
+        .may_load(deps.storage, &wallet)?
+        .ok_or(ContractError::SigningKeyNotSet {})?;
+    if signing_key != pubkey {
+        return Err(ContractError::SigningKeyMismatch {});
+    }
+    let verified = deps
+        .api
+        .secp256k1_verify(&payload.sign_bytes()?, &signature, &pubkey)
+        .map_err(|_| ContractError::InvalidSignature {})?;
+    if !verified {
+        return Err(ContractError::InvalidSignature {});
+    }
+    let nonce = SIGNED_NONCES
+        .may_load(deps.storage, &wallet)?
+        .unwrap_or(0u64);
+    payload.assert_is_valid(&env, nonce)?;
+    // the relayer's fee goes on the debt before the batch, so a repay
+    // policy that repays on any tx can cover it straight away
+    if let Some(relayer_fee) = payload.relayer_fee {
+        lend_fee_debt(
+            deps.storage,
+            &env,
+            &info.sender,
+            relayer_fee,
+            "execute_signed".to_string(),
+        )?;
+    }
+    SIGNED_NONCES.save(deps.storage, &wallet, &(nonce + 1))?;
+    let wallet_info = MessageInfo {
+        sender: wallet.clone(),
+        funds: vec![],
+    };
+    let res = execute_execute(&mut deps, env, wallet_info, payload.msgs, false)?;
+    Ok(res
+        .add_attribute("action", "execute_signed")
+        .add_attribute("nonce", nonce.to_string())
+        .add_event(signed_execute_event(
+            &info.sender,
+            &wallet,
+            nonce,
+            payload.relayer_fee,
+        )))
+}
+
+pub fn add_subscription(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    subscription: Subscription,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    deps.api.addr_validate(&subscription.recipient)?;
+    subscription.assert_is_valid()?;
+    if env.block.time >= subscription.next_due {
+        return Err(ContractError::SubscriptionInPast {});
+    }
+    // it would never pay out
+    if subscription.is_finished() {
+        return Err(ContractError::SubscriptionEndsBeforeFirstPayment {});
+    }
+    let id = SUBSCRIPTION_COUNT.may_load(deps.storage)?.unwrap_or(0u64);
+    SUBSCRIPTIONS.save(deps.storage, id, &subscription)?;
+    SUBSCRIPTION_COUNT.save(deps.storage, &(id + 1))?;
+    Ok(Response::new()
+        .add_attribute("action", "add_subscription")
+        .add_attribute("subscription_id", id.to_string())
+        .add_event(subscription_event(
+            "add_subscription",
+            &info.sender,
+            id,
+            &[],
+        )))
+}
+
+pub fn cancel_subscription(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !SUBSCRIPTIONS.has(deps.storage, id) {
+        return Err(ContractError::SubscriptionDoesNotExist {});
+    }
+    SUBSCRIPTIONS.remove(deps.storage, id);
+    Ok(Response::new()
+        .add_attribute("action", "cancel_subscription")
+        .add_attribute("subscription_id", id.to_string())
+        .add_event(subscription_event(
+            "cancel_subscription",
+            &info.sender,
+            id,
+            &[],
+        )))
+}
+
+pub fn trigger_payments(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if cfg.is_frozen() {
+        return Err(ContractError::Frozen {});
+    }
+    // ids that aren't due are skipped, so one keeper can trigger a
+    // whole list without checking each due date first. Repeated ids
+    // are dropped, so each is paid at most one period per call
+    let mut ids = ids;
+    ids.sort_unstable();
+    ids.dedup();
+    let mut res = Response::new().add_attribute("action", "trigger_payments");
+    let mut paid_any = false;
+    for id in ids {
+        let mut subscription = match SUBSCRIPTIONS.may_load(deps.storage, id)? {
+            Some(subscription) if subscription.is_due(env.block.time) => subscription,
+            _ => continue,
+        };
+        res = res
+            .add_message(BankMsg::Send {
+                to_address: subscription.recipient.clone(),
+                amount: subscription.amount.clone(),
+            })
+            .add_attribute("subscription_id", id.to_string())
+            .add_event(subscription_event(
+                "pay_subscription",
+                &info.sender,
+                id,
+                &subscription.amount,
+            ));
+        subscription.advance()?;
+        if subscription.is_finished() {
+            SUBSCRIPTIONS.remove(deps.storage, id);
+        } else {
+            SUBSCRIPTIONS.save(deps.storage, id, &subscription)?;
+        }
+        paid_any = true;
+    }
+    if !paid_any {
+        return Err(ContractError::NoPaymentsDue {});
+    }
+    Ok(res)
+}
+
+pub fn schedule_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CosmosMsg,
+    execute_after: Timestamp,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time >= execute_after {
+        return Err(ContractError::ScheduledTransferInPast {});
+    }
+    // classified now, so a message that could never go out is
+    // rejected instead of waiting in the queue
+    let mut processed_msg = PendingSubmsg::new(msg.clone());
+    let msg_type = processed_msg.process_and_get_msg_type();
+    ScheduledTransfer::assert_is_transfer(&msg_type)?;
+    let id = SCHEDULED_TRANSFER_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or(0u64);
+    SCHEDULED_TRANSFERS.save(
+        deps.storage,
+        id,
+        &ScheduledTransfer {
+            msg,
+            msg_type,
+            spend: processed_msg.funds,
+            execute_after,
+        },
+    )?;
+    SCHEDULED_TRANSFER_COUNT.save(deps.storage, &(id + 1))?;
+    Ok(Response::new()
+        .add_attribute("action", "schedule_transfer")
+        .add_attribute("transfer_id", id.to_string())
+        .add_event(scheduled_transfer_event(
+            "schedule_transfer",
+            &info.sender,
+            id,
+        )))
+}
+
+pub fn cancel_scheduled_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let transfer = SCHEDULED_TRANSFERS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ScheduledTransferDoesNotExist {})?;
+    if transfer.is_matured(env.block.time) {
+        return Err(ContractError::ScheduledTransferMatured {});
+    }
+    SCHEDULED_TRANSFERS.remove(deps.storage, id);
+    Ok(Response::new()
+        .add_attribute("action", "cancel_scheduled_transfer")
+        .add_attribute("transfer_id", id.to_string())
+        .add_event(scheduled_transfer_event(
+            "cancel_scheduled_transfer",
+            &info.sender,
+            id,
+        )))
+}
+
+pub fn execute_scheduled_transfer(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    let transfer = SCHEDULED_TRANSFERS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ScheduledTransferDoesNotExist {})?;
+    if cfg.is_frozen() {
+        return Err(ContractError::Frozen {});
+    }
+    if !transfer.is_matured(env.block.time) {
+        return Err(ContractError::ScheduledTransferNotMatured(
+            transfer.execute_after.seconds(),
+        ));
+    }
+    SCHEDULED_TRANSFERS.remove(deps.storage, id);
+    // scheduled by admin, so it goes out with admin's authority as
+    // approved proposals do: no spend limits, but fee repayment applies
+    let admin_info = MessageInfo {
+        sender: cfg.admin,
+        funds: vec![],
+    };
+    let res = execute_execute(&mut deps, env, admin_info, vec![transfer.msg], false)?;
+    Ok(res
+        .add_attribute("action", "execute_scheduled_transfer")
+        .add_attribute("transfer_id", id.to_string())
+        .add_event(scheduled_transfer_event(
+            "execute_scheduled_transfer",
+            &info.sender,
+            id,
+        )))
+}
+
+pub fn set_guardians(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    guardians: Vec<String>,
+    threshold: u32,
+    recovery_delay: u64,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut valid_guardians: Vec<Addr> = vec![];
+    for guardian in guardians {
+        let guardian = deps.api.addr_validate(&guardian)?;
+        if !valid_guardians.contains(&guardian) {
+            valid_guardians.push(guardian);
+        }
+    }
+    let guardian_set = GuardianSet {
+        guardians: valid_guardians,
+        threshold,
+        recovery_delay,
+    };
+    guardian_set.assert_is_valid()?;
+    GUARDIANS.save(deps.storage, &guardian_set)?;
+    // approvals were given under the old guardian set
+    RECOVERY.remove(deps.storage);
+    Ok(Response::new()
+        .add_attribute("action", "set_guardians")
+        .add_event(guardian_event("set_guardians", &info.sender)))
+}
+
+fn load_guardian(deps: Deps, sender: &Addr) -> Result<GuardianSet, ContractError> {
+    match GUARDIANS.may_load(deps.storage)? {
+        Some(guardian_set) if guardian_set.is_guardian(sender.to_string()) => Ok(guardian_set),
+        _ => Err(ContractError::CallerIsNotGuardian {}),
+    }
+}
+
+pub fn propose_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+) -> Result<Response, ContractError> {
+    let guardian_set = load_guardian(deps.as_ref(), &info.sender)?;
+    let new_admin = deps.api.addr_validate(&new_admin)?;
+    let backers = match RECOVERY.may_load(deps.storage)? {
+        // a pending recovery is only replaced once a threshold of
+        // guardians backs another new admin
+        Some(mut pending) if !pending.is_expired(env.block.time) => {
+            if pending.new_admin == new_admin {
+                return Err(ContractError::RecoveryAlreadyProposed {});
+            }
+            let backers = pending.back_rival(info.sender.clone(), new_admin.clone());
+            if (backers.len() as u32) < guardian_set.threshold {
+                RECOVERY.save(deps.storage, &pending)?;
+                return Ok(Response::new()
+                    .add_attribute("action", "back_rival_recovery")
+                    .add_attribute("new_admin", new_admin.clone())
+                    .add_event(
+                        guardian_event("back_rival_recovery", &info.sender)
+                            .add_attribute("new_admin", new_admin),
+                    ));
+            }
+            backers
+        }
+        _ => vec![info.sender.clone()],
+    };
+    let recovery = Recovery::new(new_admin, backers, &guardian_set, env.block.time);
+    RECOVERY.save(deps.storage, &recovery)?;
+    Ok(Response::new()
+        .add_attribute("action", "propose_recovery")
+        .add_attribute("new_admin", recovery.new_admin.clone())
+        .add_event(
+            guardian_event("propose_recovery", &info.sender)
+                .add_attribute("new_admin", recovery.new_admin),
+        ))
+}
+
+pub fn approve_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    load_guardian(deps.as_ref(), &info.sender)?;
+    let mut recovery = RECOVERY
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoRecoveryProposed {})?;
+    if recovery.is_expired(env.block.time) {
+        return Err(ContractError::RecoveryExpired(
+            recovery.expires_at.seconds(),
+        ));
+    }
+    recovery.approve(info.sender.clone());
+    RECOVERY.save(deps.storage, &recovery)?;
+    Ok(Response::new()
+        .add_attribute("action", "approve_recovery")
+        .add_event(guardian_event("approve_recovery", &info.sender)))
+}
+
+pub fn execute_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let recovery = RECOVERY
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoRecoveryProposed {})?;
+    let guardian_set = GUARDIANS.load(deps.storage)?;
+    recovery.assert_is_executable(&guardian_set, env.block.time)?;
+    let mut cfg = STATE.load(deps.storage)?;
+    let old_admin = cfg.admin.clone();
+    cfg.admin = recovery.new_admin.clone();
+    cfg.pending = recovery.new_admin;
+    cfg.admin_transfer_window = None;
+    STATE.save(deps.storage, &cfg)?;
+    RECOVERY.remove(deps.storage);
+    // signers and heir belonged to the lost admin; the new admin sets its own
+    SIGNERS.remove(deps.storage);
+    HEIR.remove(deps.storage);
+    Ok(Response::new()
+        .add_attribute("action", "execute_recovery")
+        .add_attribute("new_admin", cfg.admin.clone())
+        .add_event(admin_change_event(
+            "execute_recovery",
+            &info.sender,
+            &old_admin,
+            &cfg.admin,
+        )))
+}
+
+pub fn veto_recovery(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if RECOVERY.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoRecoveryProposed {});
+    }
+    RECOVERY.remove(deps.storage);
+    Ok(Response::new()
+        .add_attribute("action", "veto_recovery")
+        .add_event(guardian_event("veto_recovery", &info.sender)))
+}
+
+fn note_admin_activity(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+) -> Result<Option<Event>, ContractError> {
+    let mut heir = match HEIR.may_load(storage)? {
+        Some(heir) => heir,
+        None => return Ok(None),
+    };
+    let mut cfg = STATE.load(storage)?;
+    if !cfg.is_admin(sender.to_string()) {
+        return Ok(None);
+    }
+    heir.last_admin_activity = env.block.time;
+    let claim_pending = heir.claim_pending;
+    heir.claim_pending = false;
+    HEIR.save(storage, &heir)?;
+    if !claim_pending || !cfg.is_pending(heir.heir.to_string()) {
+        return Ok(None);
+    }
+    cfg.pending = cfg.admin.clone();
+    cfg.admin_transfer_window = None;
+    STATE.save(storage, &cfg)?;
+    Ok(Some(admin_change_event(
+        "cancel_inheritance",
+        sender,
+        &cfg.admin,
+        &heir.heir,
+    )))
+}
+
+pub fn set_heir(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    heir: Option<String>,
+    inactivity_seconds: u64,
+) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    match heir {
+        Some(heir) => {
+            let heir = Heir {
+                heir: deps.api.addr_validate(&heir)?,
+                inactivity_seconds,
+                last_admin_activity: env.block.time,
+                claim_pending: false,
+            };
+            heir.assert_is_valid()?;
+            HEIR.save(deps.storage, &heir)?;
+        }
+        None => HEIR.remove(deps.storage),
+    }
+    Ok(Response::new()
+        .add_attribute("action", "set_heir")
+        .add_event(guardian_event("set_heir", &info.sender)))
+}
+
+pub fn heartbeat(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    // activity was already noted before dispatch, cancelling any claim
+    HEIR.may_load(deps.storage)?
+        .ok_or(ContractError::NoHeir {})?;
+    Ok(Response::new()
+        .add_attribute("action", "heartbeat")
+        .add_event(guardian_event("heartbeat", &info.sender)))
+}
+
+pub fn claim_inheritance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut cfg = STATE.load(deps.storage)?;
+    let mut heir = HEIR
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoHeir {})?;
+    if info.sender != heir.heir {
+        return Err(ContractError::CallerIsNotHeir {});
+    }
+    if !heir.is_claimable(env.block.time) {
+        return Err(ContractError::InheritanceNotClaimable(heir.claimable_at()));
+    }
+    // the claim is an ordinary admin proposal, so the heir confirms it
+    // and admin can cancel it the usual way
+    let grace_period = cfg
+        .admin_transfer_delay
+        .unwrap_or(0)
+        .max(INHERITANCE_GRACE_PERIOD);
+    cfg.pending = heir.heir.clone();
+    cfg.admin_transfer_window = Some(AdminTransferWindow {
+        proposed_at: env.block.time,
+        executable_at: env.block.time.plus_seconds(grace_period),
+    });
+    STATE.save(deps.storage, &cfg)?;
+    heir.claim_pending = true;
+    HEIR.save(deps.storage, &heir)?;
+    Ok(Response::new()
+        .add_attribute("action", "claim_inheritance")
+        .add_event(admin_change_event(
+            "claim_inheritance",
+            &info.sender,
+            &cfg.admin,
+            &cfg.pending,
+        )))
+}
+
+pub fn freeze(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut cfg = STATE.load(deps.storage)?;
+    let is_guardian = match GUARDIANS.may_load(deps.storage)? {
+        Some(guardian_set) => guardian_set.is_guardian(info.sender.to_string()),
+        None => false,
+    };
+    if !cfg.is_admin(info.sender.to_string())
+        && !is_guardian
+        && !cfg.is_active_hot_wallet(info.sender.clone())?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    // keep whoever froze it first
+    if !cfg.is_frozen() {
+        cfg.frozen_by = Some(info.sender.clone());
+        STATE.save(deps.storage, &cfg)?;
+    }
+    Ok(Response::new()
+        .add_attribute("action", "freeze")
+        .add_attribute("frozen_by", info.sender.clone())
+        .add_event(freeze_event("freeze", &info.sender)))
+}
+
+pub fn unfreeze(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut cfg = STATE.load(deps.storage)?;
+    if !cfg.is_admin(info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    cfg.frozen_by = None;
+    STATE.save(deps.storage, &cfg)?;
+    Ok(Response::new()
+        .add_attribute("action", "unfreeze")
+        .add_event(freeze_event("unfreeze", &info.sender)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
         QueryMsg::Admin {} => to_binary(&query_admin(deps)?),
         QueryMsg::Pending {} => to_binary(&query_pending(deps)?),
-        QueryMsg::CanExecute { sender, msg } => to_binary(&query_can_execute(deps, sender, msg)?),
+        QueryMsg::PendingAdmin {} => to_binary(&query_pending_admin(deps)?),
+        QueryMsg::CanExecute { sender, msg } => {
+            to_binary(&query_can_execute(deps, env, sender, msg)?)
+        }
         QueryMsg::HotWallets {} => to_binary(&query_hot_wallets(deps)?),
+        QueryMsg::SpendLimitStatus { wallet } => {
+            to_binary(&query_spend_limit_status(deps, env, wallet)?)
+        }
         QueryMsg::CanSpend { sender, msgs } => {
             to_binary(&query_can_spend(deps, env, sender, msgs)?)
         }
+        QueryMsg::SimulateExecute { sender, msgs } => {
+            to_binary(&query_simulate_execute(deps, env, sender, msgs)?)
+        }
+        QueryMsg::FeeDebt {} => to_binary(&query_fee_debt(deps)?),
+        QueryMsg::FeeDebtHistory { start_after, limit } => {
+            to_binary(&query_fee_debt_history(deps, start_after, limit)?)
+        }
+        QueryMsg::SpendHistory {
+            wallet,
+            start_after,
+            limit,
+        } => to_binary(&query_spend_history(deps, wallet, start_after, limit)?),
+        QueryMsg::Signers {} => to_binary(&query_signers(deps)?),
+        QueryMsg::Proposal { id } => to_binary(&query_proposal(deps, env, id)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_binary(&query_proposals(deps, env, start_after, limit)?)
+        }
+        QueryMsg::Guardians {} => to_binary(&query_guardians(deps)?),
+        QueryMsg::RecoveryStatus {} => to_binary(&query_recovery_status(deps, env)?),
+        QueryMsg::Heir {} => to_binary(&query_heir(deps, env)?),
+        QueryMsg::ScheduledTransfers { start_after, limit } => {
+            to_binary(&query_scheduled_transfers(deps, start_after, limit)?)
+        }
+        QueryMsg::Subscriptions { start_after, limit } => {
+            to_binary(&query_subscriptions(deps, start_after, limit)?)
+        }
+        QueryMsg::SessionKeys {} => to_binary(&query_session_keys(deps)?),
+        QueryMsg::SignedNonce { wallet } => to_binary(&query_signed_nonce(deps, wallet)?),
     }
 }
 
@@ -423,13 +1972,34 @@ pub fn query_pending(deps: Deps) -> StdResult<AdminResponse> {
     })
 }
 
+pub fn query_pending_admin(deps: Deps) -> StdResult<PendingAdminResponse> {
+    let cfg = STATE.load(deps.storage)?;
+    // pending is the admin itself when nothing is proposed
+    if cfg.pending == cfg.admin {
+        return Ok(PendingAdminResponse {
+            pending: None,
+            proposed_at: None,
+            executable_at: None,
+        });
+    }
+    let window = cfg.admin_transfer_window;
+    Ok(PendingAdminResponse {
+        pending: Some(cfg.pending.to_string()),
+        proposed_at: window.clone().map(|w| w.proposed_at),
+        executable_at: window.map(|w| w.executable_at),
+    })
+}
+
 pub fn query_can_execute(
     deps: Deps,
+    env: Env,
     sender: String,
-    _msg: CosmosMsg,
+    msg: CosmosMsg,
 ) -> StdResult<CanExecuteResponse> {
+    // same checks as execute_execute, so hot wallets are answered for too
+    let simulation = query_simulate_execute(deps, env, sender, vec![msg])?;
     Ok(CanExecuteResponse {
-        can_execute: can_execute(deps, &sender)?,
+        can_execute: simulation.can_execute,
     })
 }
 
@@ -440,6 +2010,325 @@ pub fn query_hot_wallets(deps: Deps) -> StdResult<HotWalletsResponse> {
     })
 }
 
+pub fn query_session_keys(deps: Deps) -> StdResult<SessionKeysResponse> {
+    let session_keys = SESSION_KEYS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, session_key)| session_key))
+        .collect::<StdResult<Vec<SessionKey>>>()?;
+    Ok(SessionKeysResponse { session_keys })
+}
+
+pub fn query_spend_limit_status(
+    deps: Deps,
+    env: Env,
+    wallet: String,
+) -> StdResult<SpendLimitStatusResponse> {
+    let cfg = STATE.load(deps.storage)?;
+    // limit_remaining is only reset lazily on the next spend, so apply
+    // any reset that is already due
+    let hot_wallet = match cfg
+        .maybe_get_hot_wallet(wallet.clone())
+        .and_then(|hot_wallet| hot_wallet.with_pending_reset(env.block.time))
+    {
+        Ok(hot_wallet) => hot_wallet,
+        Err(e) => return Err(StdError::GenericErr { msg: e.to_string() }),
+    };
+    let spend_limit = match hot_wallet.spend_limits.first() {
+        Some(spend_limit) => spend_limit.clone(),
+        None => {
+            return Err(StdError::GenericErr {
+                msg: ContractError::NoSpendLimit {}.to_string(),
+            })
+        }
+    };
+
+    // Timestamp keeps nanoseconds in a u64, which long MONTHS periods can overflow
+    let period_end = match hot_wallet.current_period_reset.checked_mul(1_000_000_000) {
+        Some(nanos) => Timestamp::from_nanos(nanos),
+        None => {
+            return Err(StdError::GenericErr {
+                msg: ContractError::DayUpdateError("time out of range".to_string()).to_string(),
+            })
+        }
+    };
+
+    let mut denoms: Vec<String> = vec![];
+    for pair_contract in cfg.pair_contracts.iter() {
+        for denom in [pair_contract.denom1.clone(), pair_contract.denom2.clone()] {
+            if denom != MAINNET_AXLUSDC_IBC && !denoms.contains(&denom) {
+                denoms.push(denom);
+            }
+        }
+    }
+    // a denom that can't be priced right now is left out
+    let native_equivalents = denoms
+        .into_iter()
+        .filter_map(|denom| {
+            SourcedCoin {
+                coin: Coin {
+                    denom,
+                    amount: Uint128::from(spend_limit.limit_remaining),
+                },
+                wrapped_sources: Sources { sources: vec![] },
+            }
+            .get_converted_to_usdc(deps, true)
+            .ok()
+        })
+        .collect();
+
+    Ok(SpendLimitStatusResponse {
+        wallet,
+        paused: hot_wallet.is_paused(),
+        limit: spend_limit.amount,
+        limit_remaining: spend_limit.limit_remaining,
+        period_end,
+        native_equivalents,
+    })
+}
+
+pub fn query_fee_debt(deps: Deps) -> StdResult<FeeDebtResponse> {
+    let cfg = STATE.load(deps.storage)?;
+    // quote is in the home network's native asset, which is what
+    // repayment is usually attached to
+    let repay_quote = if cfg.uusd_fee_debt > Uint128::from(0u128) {
+        match convert_debt_to_asset_spent(deps, cfg.uusd_fee_debt, cfg.get_home_denom()?) {
+            Ok(quote) => Some(quote),
+            Err(e) => return Err(StdError::GenericErr { msg: e.to_string() }),
+        }
+    } else {
+        None
+    };
+    Ok(FeeDebtResponse {
+        uusd_fee_debt: cfg.uusd_fee_debt,
+        max_fee_debt: cfg.max_fee_debt,
+        fee_repay_policy: cfg.get_fee_repay_policy(),
+        fee_lend_repay_wallet: cfg.fee_lend_repay_wallet.to_string(),
+        repay_quote,
+    })
+}
+
+pub fn query_fee_debt_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<FeeDebtHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let records = FEE_DEBT_HISTORY
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(FeeDebtHistoryResponse { records })
+}
+
+pub fn query_spend_history(
+    deps: Deps,
+    wallet: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SpendHistoryResponse> {
+    let wallet = deps.api.addr_validate(&wallet)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let records = SPEND_HISTORY
+        .prefix(wallet)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(SpendHistoryResponse { records })
+}
+
+pub fn query_signers(deps: Deps) -> StdResult<SignersResponse> {
+    let cfg = STATE.load(deps.storage)?;
+    let signers = match SIGNERS.may_load(deps.storage)? {
+        Some(signer_set) if signer_set.admin == cfg.admin => signer_set
+            .signers
+            .into_iter()
+            .map(|signer| signer.to_string())
+            .collect(),
+        _ => vec![],
+    };
+    Ok(SignersResponse {
+        admin: cfg.admin.to_string(),
+        signers,
+    })
+}
+
+fn to_proposal_response(
+    deps: Deps,
+    cfg: &State,
+    env: &Env,
+    id: u64,
+    proposal: Proposal,
+) -> StdResult<ProposalResponse> {
+    // counted as ExecuteProposal would count them
+    let counted = Proposal {
+        approvals: current_approvals(deps, cfg, &proposal)?,
+        ..proposal.clone()
+    };
+    let executable = match cfg.proposal_threshold {
+        Some(threshold) => counted
+            .assert_is_executable(threshold, env.block.time)
+            .is_ok(),
+        None => false,
+    };
+    Ok(ProposalResponse {
+        id,
+        proposal,
+        executable,
+    })
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<ProposalResponse> {
+    let cfg = STATE.load(deps.storage)?;
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_proposal_response(deps, &cfg, &env, id, proposal)
+}
+
+pub fn query_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let cfg = STATE.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let proposals = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.and_then(|(id, proposal)| to_proposal_response(deps, &cfg, &env, id, proposal))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ProposalsResponse { proposals })
+}
+
+pub fn query_scheduled_transfers(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ScheduledTransfersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let transfers = SCHEDULED_TRANSFERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ScheduledTransfersResponse { transfers })
+}
+
+pub fn query_subscriptions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SubscriptionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let subscriptions = SUBSCRIPTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(SubscriptionsResponse { subscriptions })
+}
+
+pub fn query_guardians(deps: Deps) -> StdResult<GuardiansResponse> {
+    Ok(GuardiansResponse {
+        guardians: GUARDIANS.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_heir(deps: Deps, env: Env) -> StdResult<HeirResponse> {
+    let heir = HEIR.may_load(deps.storage)?;
+    let claimable = match &heir {
+        Some(heir) => heir.is_claimable(env.block.time),
+        None => false,
+    };
+    Ok(HeirResponse { heir, claimable })
+}
+
+pub fn query_recovery_status(deps: Deps, env: Env) -> StdResult<RecoveryStatusResponse> {
+    let recovery = RECOVERY.may_load(deps.storage)?;
+    let guardian_set = GUARDIANS.may_load(deps.storage)?;
+    let executable = match (&recovery, &guardian_set) {
+        (Some(recovery), Some(guardian_set)) => recovery
+            .assert_is_executable(guardian_set, env.block.time)
+            .is_ok(),
+        _ => false,
+    };
+    Ok(RecoveryStatusResponse {
+        recovery,
+        threshold: guardian_set.map(|set| set.threshold).unwrap_or(0),
+        executable,
+    })
+}
+
+pub fn query_signed_nonce(deps: Deps, wallet: String) -> StdResult<SignedNonceResponse> {
+    let wallet = deps.api.addr_validate(&wallet)?;
+    Ok(SignedNonceResponse {
+        wallet: wallet.to_string(),
+        next_nonce: SIGNED_NONCES
+            .may_load(deps.storage, &wallet)?
+            .unwrap_or(0u64),
+        pubkey: SIGNING_KEYS.may_load(deps.storage, &wallet)?,
+    })
+}
+
+fn unsimulated_msg(msg: CosmosMsg, error: Option<String>) -> MsgSimulation {
+    let mut processed_msg = PendingSubmsg::new(msg);
+    MsgSimulation {
+        msg_type: processed_msg.process_and_get_msg_type(),
+        usd_value: None,
+        fee_repay: None,
+        limit_remaining: None,
+        error,
+    }
+}
+
+pub fn query_simulate_execute(
+    deps: Deps,
+    env: Env,
+    sender: String,
+    msgs: Vec<CosmosMsg>,
+) -> StdResult<SimulateExecuteResponse> {
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(&sender)?,
+        funds: vec![],
+    };
+    // the real execute pipeline, with its writes kept in an overlay
+    // that is dropped afterwards
+    let mut overlay = OverlayStorage::new(deps.storage);
+    let mut sim_deps = DepsMut {
+        storage: &mut overlay,
+        api: deps.api,
+        querier: deps.querier,
+    };
+    let mut trace = ExecuteTrace {
+        keep_going: true,
+        ..ExecuteTrace::default()
+    };
+    let result = traced_execute(&mut sim_deps, env, info, msgs.clone(), true, &mut trace);
+    // rejected messages carry their own errors, so an error here
+    // rejected the whole batch
+    let error = result.as_ref().err().map(|e| e.to_string());
+    let can_execute = result.is_ok() && trace.msgs.iter().all(|msg| msg.error.is_none());
+    // messages are never reached if the batch is rejected before the
+    // spend checks
+    let checked = trace.msgs.len();
+    let mut simulations = trace.msgs;
+    simulations.extend(
+        msgs.into_iter()
+            .skip(checked)
+            .map(|msg| unsimulated_msg(msg, None)),
+    );
+    Ok(SimulateExecuteResponse {
+        can_execute,
+        fee_repay: trace.fee_repay,
+        error,
+        msgs: simulations,
+    })
+}
+
 pub fn query_can_spend(
     deps: Deps,
     env: Env,
@@ -450,7 +2339,23 @@ pub fn query_can_spend(
     // if admin, always – though technically this might not be true
     // if first token send with nothing left to repay fees
     if cfg.is_admin(sender.clone()) {
-        return Ok(CanSpendResponse { can_spend: true });
+        return Ok(CanSpendResponse {
+            can_spend: true,
+            reason: None,
+        });
+    }
+    if let Some(frozen_by) = cfg.frozen_by.clone() {
+        return Ok(CanSpendResponse {
+            can_spend: false,
+            reason: Some(format!("contract frozen by {}", frozen_by)),
+        });
+    }
+    // hot wallets are frozen once the fee repay deadline has passed
+    if cfg.is_past_fee_repay_deadline(env.block.time) {
+        return Ok(CanSpendResponse {
+            can_spend: false,
+            reason: Some("fee repay deadline passed".to_string()),
+        });
     }
     // if one of authorized token contracts and spender is hot wallet, yes
     if msgs.len() > 1 {
@@ -468,7 +2373,10 @@ pub fn query_can_spend(
             && cfg.is_authorized_hotwallet_contract(contract_addr)
             && funds == vec![]
         {
-            return Ok(CanSpendResponse { can_spend: true });
+            return Ok(CanSpendResponse {
+                can_spend: true,
+                reason: None,
+            });
         }
     };
     let funds: Vec<Coin> = match msgs[0].clone() {
@@ -510,7 +2418,13 @@ pub fn query_can_spend(
     };
     let res = cfg.check_spend_limits(deps, env.block.time, sender, funds);
     match res {
-        Ok(_) => Ok(CanSpendResponse { can_spend: true }),
-        Err(_) => Ok(CanSpendResponse { can_spend: false }),
+        Ok(_) => Ok(CanSpendResponse {
+            can_spend: true,
+            reason: None,
+        }),
+        Err(e) => Ok(CanSpendResponse {
+            can_spend: false,
+            reason: Some(e.to_string()),
+        }),
     }
 }
@@ -0,0 +1,64 @@
+//! Diagnostics for the `debug-events` feature. Deep code paths, such as
+//! pricing and period resets, record attributes with `debug_attr!`, and
+//! `execute` attaches them to its `Response` as one `obi_debug` event.
+//! Without the feature, `debug_attr!` compiles to nothing.
+
+#[cfg(feature = "debug-events")]
+use std::cell::RefCell;
+
+use cosmwasm_std::Response;
+#[cfg(feature = "debug-events")]
+use cosmwasm_std::{Attribute, Event};
+
+use crate::ContractError;
+
+pub const DEBUG_EVENT: &str = "obi_debug";
+
+// contracts run single threaded, and each unit test has its own thread
+#[cfg(feature = "debug-events")]
+thread_local! {
+    static DEBUG_ATTRIBUTES: RefCell<Vec<Attribute>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(feature = "debug-events")]
+pub fn record(key: &str, value: String) {
+    DEBUG_ATTRIBUTES.with(|attributes| attributes.borrow_mut().push(Attribute::new(key, value)));
+}
+
+#[cfg(feature = "debug-events")]
+macro_rules! debug_attr {
+    ($key:expr, $value:expr) => {
+        $crate::debug::record($key, $value.to_string())
+    };
+}
+
+#[cfg(not(feature = "debug-events"))]
+macro_rules! debug_attr {
+    ($key:expr, $value:expr) => {};
+}
+
+pub(crate) use debug_attr;
+
+/// Runs `execute`, adding anything recorded along the way as a debug event
+#[cfg(feature = "debug-events")]
+pub fn with_debug_event<F>(execute: F) -> Result<Response, ContractError>
+where
+    F: FnOnce() -> Result<Response, ContractError>,
+{
+    // drop anything left by queries, which can't emit events
+    DEBUG_ATTRIBUTES.with(|attributes| attributes.borrow_mut().clear());
+    let res = execute()?;
+    let attributes = DEBUG_ATTRIBUTES.with(|attributes| attributes.replace(vec![]));
+    match attributes.is_empty() {
+        true => Ok(res),
+        false => Ok(res.add_event(Event::new(DEBUG_EVENT).add_attributes(attributes))),
+    }
+}
+
+#[cfg(not(feature = "debug-events"))]
+pub fn with_debug_event<F>(execute: F) -> Result<Response, ContractError>
+where
+    F: FnOnce() -> Result<Response, ContractError>,
+{
+    execute()
+}
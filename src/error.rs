@@ -43,6 +43,9 @@ pub enum ContractError {
     #[error("This address is not authorized as a spend limit Hot Wallet.")]
     HotWalletDoesNotExist {},
 
+    #[error("This Hot Wallet is paused. Admin must unpause it first.")]
+    HotWalletPaused {},
+
     #[error("Failed to advance the reset day: {0}")]
     DayUpdateError(String),
 
@@ -52,6 +55,9 @@ pub enum ContractError {
     #[error("Hot wallet does not have a spend limit for asset {0}.")]
     CannotSpendThisAsset(String),
 
+    #[error("Hot wallet has no spend limit.")]
+    NoSpendLimit {},
+
     #[error("You cannot spend more than your available spend limit. Trying to spend {0} {1}")]
     CannotSpendMoreThanLimit(String, String),
 
@@ -61,6 +67,9 @@ pub enum ContractError {
     #[error("Caller is not pending new admin. Propose new admin first.")]
     CallerIsNotPendingNewAdmin {},
 
+    #[error("New admin cannot be confirmed until {0} (seconds).")]
+    AdminTransferTimelocked(u64),
+
     #[error("Unable to get current asset price to check spend limit for asset. If this transaction is urgent, use your multisig to sign. SUBMSG: {0} CONTRACT: {1} ERROR: {2}")]
     PriceCheckFailed(String, String, String),
 
@@ -90,6 +99,140 @@ pub enum ContractError {
 
     #[error("Unable to pay debt of {0} uusd")]
     UnableToRepayDebt(String),
+
+    #[error("There is no fee debt to repay.")]
+    NoFeeDebt {},
+
+    #[error("Caller is not the fee lender.")]
+    CallerIsNotFeeLender {},
+
+    #[error("Fee debt of {0} uusd would exceed the maximum of {1} uusd.")]
+    FeeDebtOverMaximum(String, String),
+
+    #[error(
+        "The fee repay deadline has passed. Hot wallets are frozen until the fee debt is repaid."
+    )]
+    FeeRepayDeadlinePassed {},
+
+    #[error("Caller is not a guardian.")]
+    CallerIsNotGuardian {},
+
+    #[error("Guardian threshold must be at least 1 and no more than the number of guardians.")]
+    InvalidGuardianThreshold {},
+
+    #[error("This recovery is already proposed. Guardians can approve it instead.")]
+    RecoveryAlreadyProposed {},
+
+    #[error("No recovery is proposed.")]
+    NoRecoveryProposed {},
+
+    #[error("Recovery has {0} of {1} required guardian approvals.")]
+    RecoveryNotApproved(u32, u32),
+
+    #[error("Recovery cannot be executed until {0} (seconds).")]
+    RecoveryTimelocked(u64),
+
+    #[error("Recovery expired at {0} (seconds). A guardian can propose a new one.")]
+    RecoveryExpired(u64),
+
+    #[error("Contract is frozen. Admin must unfreeze it first.")]
+    Frozen {},
+
+    #[error("Proposals are not enabled. Admin must set a proposal threshold first.")]
+    ProposalsNotEnabled {},
+
+    #[error("Proposal threshold must be at least 2.")]
+    InvalidProposalThreshold {},
+
+    #[error("Proposal does not exist.")]
+    ProposalDoesNotExist {},
+
+    #[error("Proposal has expired.")]
+    ProposalExpired {},
+
+    #[error("Proposal has already been executed.")]
+    ProposalAlreadyExecuted {},
+
+    #[error("Proposal has {0} of {1} required approvals.")]
+    ProposalNotApproved(u32, u32),
+
+    #[error("Signing key must be a compressed or uncompressed secp256k1 public key.")]
+    InvalidSigningKey {},
+
+    #[error("Hot wallet has not set a signing key.")]
+    SigningKeyNotSet {},
+
+    #[error("Public key does not match the hot wallet's signing key.")]
+    SigningKeyMismatch {},
+
+    #[error("Signature does not match the payload.")]
+    InvalidSignature {},
+
+    #[error("Signed payload is for another proxy or chain.")]
+    WrongSignedPayloadTarget {},
+
+    #[error("Signed payload has expired.")]
+    SignedPayloadExpired {},
+
+    #[error("Expected nonce {0} but the payload has {1}.")]
+    BadNonce(u64, u64),
+
+    #[error("This address is already a session key, hot wallet or admin.")]
+    SessionKeyExists {},
+
+    #[error("This address is not a session key.")]
+    SessionKeyDoesNotExist {},
+
+    #[error("Session key has expired.")]
+    SessionKeyExpired {},
+
+    #[error("Session key can only execute messages on {0}.")]
+    SessionKeyWrongContract(String),
+
+    #[error("Session key is not allowed to send {0}.")]
+    SessionKeyMsgNotAllowed(String),
+
+    #[error("Session key spend of {0} micro USD would exceed its cap of {1}.")]
+    SessionKeyOverCap(String, String),
+
+    #[error("Subscription does not exist.")]
+    SubscriptionDoesNotExist {},
+
+    #[error("Subscription's first payment must be after the current block time.")]
+    SubscriptionInPast {},
+
+    #[error("Subscription must end after its first payment.")]
+    SubscriptionEndsBeforeFirstPayment {},
+
+    #[error("None of these subscriptions are due.")]
+    NoPaymentsDue {},
+
+    #[error("Scheduled transfer does not exist.")]
+    ScheduledTransferDoesNotExist {},
+
+    #[error("Scheduled transfer must be after the current block time.")]
+    ScheduledTransferInPast {},
+
+    #[error("Scheduled transfer cannot be executed until {0} (seconds).")]
+    ScheduledTransferNotMatured(u64),
+
+    #[error("Scheduled transfer has matured and can no longer be cancelled.")]
+    ScheduledTransferMatured {},
+
+    #[error("No heir is set.")]
+    NoHeir {},
+
+    #[error("Caller is not the heir.")]
+    CallerIsNotHeir {},
+
+    #[error("Hot wallet has already made its {0} transactions for this period.")]
+    TooManyTransactions(u32),
+
+    #[error("Hot wallet cannot make another transaction until {0} (seconds).")]
+    TransactionTooSoon(u64),
+
+    #[error("Inheritance cannot be claimed until {0} (seconds), unless admin stays inactive.")]
+    InheritanceNotClaimable(u64),
 }
 
 impl From<semver::Error> for ContractError {
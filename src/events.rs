@@ -0,0 +1,177 @@
+//! Typed events emitted by the proxy, so that indexers can follow spends,
+//! fee repayments and configuration changes without parsing actions.
+//! CosmWasm prefixes each type with "wasm-" on chain, so `obi_spend` is
+//! indexed as `wasm-obi_spend`.
+//!
+//! | event                    | emitted on                                     | keys                                                                         |
+//! |--------------------------|------------------------------------------------|------------------------------------------------------------------------------|
+//! | `obi_spend`              | a hot wallet spend                             | wallet, msg_summary, usd_value, denom, amount, price_source, limit_remaining |
+//! | `obi_fee_repay`          | fee debt repayment                             | wallet, repay_to, usd_value, denom, amount, price_source                     |
+//! | `obi_fee_debt`           | fee debt, cap or repay policy changes          | action, wallet, usd_value (if any)                                           |
+//! | `obi_admin_change`       | admin proposals, transfers and recovery        | action, wallet, admin, new_admin                                             |
+//! | `obi_hot_wallet`         | hot wallets added, removed, paused or unpaused | action, wallet, hot_wallet                                                   |
+//! | `obi_guardian`           | guardian, recovery and heir changes            | action, wallet                                                               |
+//! | `obi_proposal`           | spend proposals                                | action, wallet, proposal_id                                                  |
+//! | `obi_session_key`        | session keys created, revoked or used          | action, wallet, session_key, usd_value (if used)                             |
+//! | `obi_signed_execute`     | a relayed, hot-wallet-signed batch             | action, wallet, signer, nonce, usd_value (relayer fee, if any)               |
+//! | `obi_scheduled_transfer` | transfers scheduled, cancelled or executed     | action, wallet, transfer_id                                                  |
+//! | `obi_subscription`       | subscriptions added, cancelled or paid         | action, wallet, subscription_id, denom, amount (if paid)                     |
+//! | `obi_freeze`             | freezing and unfreezing                        | action, wallet                                                               |
+//! | `obi_config`             | other admin settings                           | action, wallet, value                                                        |
+//!
+//! Builds with the `debug-events` feature also emit `obi_debug`, with
+//! pricing and period reset diagnostics (see src/debug.rs).
+//!
+//! `wallet` is always the address that sent the message. `denom` and
+//! `amount` repeat once per coin, and `price_source` once per contract
+//! queried to value the coins in USD.
+
+use cosmwasm_std::{Addr, Attribute, Coin, Event, Uint128};
+
+use crate::sources::Sources;
+use crate::state::SpendRecord;
+
+pub const SPEND_EVENT: &str = "obi_spend";
+pub const FEE_REPAY_EVENT: &str = "obi_fee_repay";
+pub const FEE_DEBT_EVENT: &str = "obi_fee_debt";
+pub const ADMIN_CHANGE_EVENT: &str = "obi_admin_change";
+pub const HOT_WALLET_EVENT: &str = "obi_hot_wallet";
+pub const GUARDIAN_EVENT: &str = "obi_guardian";
+pub const PROPOSAL_EVENT: &str = "obi_proposal";
+pub const SESSION_KEY_EVENT: &str = "obi_session_key";
+pub const SIGNED_EXECUTE_EVENT: &str = "obi_signed_execute";
+pub const SCHEDULED_TRANSFER_EVENT: &str = "obi_scheduled_transfer";
+pub const SUBSCRIPTION_EVENT: &str = "obi_subscription";
+pub const FREEZE_EVENT: &str = "obi_freeze";
+pub const CONFIG_EVENT: &str = "obi_config";
+
+fn coin_attributes(coins: &[Coin]) -> Vec<Attribute> {
+    let mut attributes: Vec<Attribute> = vec![];
+    for coin in coins {
+        attributes.push(Attribute::new("denom", coin.denom.clone()));
+        attributes.push(Attribute::new("amount", coin.amount));
+    }
+    attributes
+}
+
+fn price_source_attributes(sources: &Sources) -> Vec<Attribute> {
+    sources
+        .sources
+        .iter()
+        .map(|source| Attribute::new("price_source", source.contract_addr.clone()))
+        .collect()
+}
+
+fn action_event(ty: &str, action: &str, wallet: &Addr) -> Event {
+    Event::new(ty)
+        .add_attribute("action", action)
+        .add_attribute("wallet", wallet)
+}
+
+pub fn spend_event(wallet: &Addr, record: &SpendRecord, limit_remaining: Option<u64>) -> Event {
+    let mut event = Event::new(SPEND_EVENT)
+        .add_attribute("wallet", wallet)
+        .add_attribute("msg_summary", record.msg_summary.clone())
+        .add_attribute("usd_value", record.usd_amount)
+        .add_attributes(coin_attributes(&record.coins))
+        .add_attributes(price_source_attributes(&record.sources));
+    if let Some(limit_remaining) = limit_remaining {
+        event = event.add_attribute("limit_remaining", limit_remaining.to_string());
+    }
+    event
+}
+
+pub fn fee_repay_event(
+    wallet: &Addr,
+    repay_to: &str,
+    usd_value: Uint128,
+    repaid: &[Coin],
+    sources: &Sources,
+) -> Event {
+    Event::new(FEE_REPAY_EVENT)
+        .add_attribute("wallet", wallet)
+        .add_attribute("repay_to", repay_to)
+        .add_attribute("usd_value", usd_value)
+        .add_attributes(coin_attributes(repaid))
+        .add_attributes(price_source_attributes(sources))
+}
+
+pub fn fee_debt_event(action: &str, wallet: &Addr, usd_value: Option<Uint128>) -> Event {
+    let event = action_event(FEE_DEBT_EVENT, action, wallet);
+    match usd_value {
+        Some(usd_value) => event.add_attribute("usd_value", usd_value),
+        None => event,
+    }
+}
+
+pub fn admin_change_event(action: &str, wallet: &Addr, admin: &Addr, new_admin: &Addr) -> Event {
+    action_event(ADMIN_CHANGE_EVENT, action, wallet)
+        .add_attribute("admin", admin)
+        .add_attribute("new_admin", new_admin)
+}
+
+pub fn hot_wallet_event(action: &str, wallet: &Addr, hot_wallet: &str) -> Event {
+    action_event(HOT_WALLET_EVENT, action, wallet).add_attribute("hot_wallet", hot_wallet)
+}
+
+pub fn guardian_event(action: &str, wallet: &Addr) -> Event {
+    action_event(GUARDIAN_EVENT, action, wallet)
+}
+
+pub fn proposal_event(action: &str, wallet: &Addr, proposal_id: u64) -> Event {
+    action_event(PROPOSAL_EVENT, action, wallet)
+        .add_attribute("proposal_id", proposal_id.to_string())
+}
+
+pub fn session_key_event(
+    action: &str,
+    wallet: &Addr,
+    session_key: &str,
+    usd_value: Option<Uint128>,
+) -> Event {
+    let event =
+        action_event(SESSION_KEY_EVENT, action, wallet).add_attribute("session_key", session_key);
+    match usd_value {
+        Some(usd_value) => event.add_attribute("usd_value", usd_value),
+        None => event,
+    }
+}
+
+pub fn signed_execute_event(
+    wallet: &Addr,
+    signer: &Addr,
+    nonce: u64,
+    relayer_fee: Option<Uint128>,
+) -> Event {
+    let event = action_event(SIGNED_EXECUTE_EVENT, "execute_signed", wallet)
+        .add_attribute("signer", signer)
+        .add_attribute("nonce", nonce.to_string());
+    match relayer_fee {
+        Some(relayer_fee) => event.add_attribute("usd_value", relayer_fee),
+        None => event,
+    }
+}
+
+pub fn scheduled_transfer_event(action: &str, wallet: &Addr, transfer_id: u64) -> Event {
+    action_event(SCHEDULED_TRANSFER_EVENT, action, wallet)
+        .add_attribute("transfer_id", transfer_id.to_string())
+}
+
+pub fn subscription_event(
+    action: &str,
+    wallet: &Addr,
+    subscription_id: u64,
+    paid: &[Coin],
+) -> Event {
+    action_event(SUBSCRIPTION_EVENT, action, wallet)
+        .add_attribute("subscription_id", subscription_id.to_string())
+        .add_attributes(coin_attributes(paid))
+}
+
+pub fn freeze_event(action: &str, wallet: &Addr) -> Event {
+    action_event(FREEZE_EVENT, action, wallet)
+}
+
+pub fn config_event(action: &str, wallet: &Addr, value: impl Into<String>) -> Event {
+    action_event(CONFIG_EVENT, action, wallet).add_attribute("value", value)
+}
@@ -4,7 +4,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    constants::MAINNET_AXLUSDC_IBC, sourced_coin::SourcedCoin, sources::Sources, ContractError,
+    constants::MAINNET_AXLUSDC_IBC, debug::debug_attr, sourced_coin::SourcedCoin, sources::Sources,
+    ContractError,
 };
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -37,14 +38,34 @@ pub struct HotWallet {
     pub spend_limits: Vec<CoinLimit>,
     pub usdc_denom: Option<String>,
     pub default: Option<bool>,
+    pub paused: Option<bool>, // None is not paused
+    // an Execute, however many messages it has, counts as one transaction
+    pub max_tx_per_period: Option<u32>,      // None is unlimited
+    pub min_seconds_between_tx: Option<u64>, // None is no minimum
+    pub tx_count: Option<u32>,               // this period; None is 0
+    pub last_tx_time: Option<u64>,           // seconds; None is never
 }
 
 impl HotWallet {
+    pub fn is_paused(&self) -> bool {
+        self.paused == Some(true)
+    }
+
     pub fn should_reset(&self, current_time: Timestamp) -> bool {
         current_time.seconds() > self.current_period_reset
     }
 
     pub fn assert_is_valid(&self) -> StdResult<()> {
+        if self.period_multiple == 0 {
+            return Err(StdError::GenericErr {
+                msg: "Hot wallet period multiple must be at least 1.".to_string(),
+            });
+        }
+        if self.max_tx_per_period == Some(0) {
+            return Err(StdError::GenericErr {
+                msg: "Hot wallet max transactions per period must be at least 1.".to_string(),
+            });
+        }
         if self.usdc_denom != Some("true".to_string())
             || self.spend_limits.len() > 1
             || (self.spend_limits[0].denom != MAINNET_AXLUSDC_IBC
@@ -62,7 +83,43 @@ impl HotWallet {
     }
 
     pub fn reset_limits(&mut self) {
-        self.spend_limits[0].limit_remaining = self.spend_limits[0].amount;
+        if let Some(limit) = self.spend_limits.first_mut() {
+            limit.limit_remaining = limit.amount;
+        }
+        self.tx_count = None;
+    }
+
+    /// errors if another transaction now would exceed the wallet's
+    /// transaction count for the period, or come too soon after the last.
+    /// `reset` counts from a fresh period
+    pub fn check_tx_limits(
+        &self,
+        current_time: Timestamp,
+        reset: bool,
+    ) -> Result<(), ContractError> {
+        if let Some(max_tx) = self.max_tx_per_period {
+            let tx_count = match reset {
+                false => self.tx_count.unwrap_or(0),
+                true => 0,
+            };
+            if tx_count >= max_tx {
+                return Err(ContractError::TooManyTransactions(max_tx));
+            }
+        }
+        if let (Some(min_seconds), Some(last_tx_time)) =
+            (self.min_seconds_between_tx, self.last_tx_time)
+        {
+            let next_tx_time = last_tx_time.saturating_add(min_seconds);
+            if current_time.seconds() < next_tx_time {
+                return Err(ContractError::TransactionTooSoon(next_tx_time));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record_tx(&mut self, current_time: Timestamp) {
+        self.tx_count = Some(self.tx_count.unwrap_or(0).saturating_add(1));
+        self.last_tx_time = Some(current_time.seconds());
     }
 
     pub fn simulate_reduce_limit(
@@ -143,52 +200,97 @@ impl HotWallet {
         Ok(spend_limit_reduction.1)
     }
 
+    /// returns the wallet as the next spend would see it, with the
+    /// period reset applied if it is due, without saving anything
+    pub fn with_pending_reset(&self, current_time: Timestamp) -> Result<HotWallet, ContractError> {
+        let mut wallet = self.clone();
+        if wallet.should_reset(current_time) {
+            wallet.reset_period(current_time)?;
+        }
+        Ok(wallet)
+    }
+
     pub fn reset_period(&mut self, current_time: Timestamp) -> Result<(), ContractError> {
-        let new_dt = NaiveDateTime::from_timestamp(current_time.seconds() as i64, 0u32);
-        // how far ahead we set new current_period_reset to
-        // depends on the spend limit period (type and multiple)
-        let new_dt: Result<NaiveDateTime, ContractError> = match self.period_type {
-            PeriodType::DAYS => {
-                let working_dt =
-                    new_dt.checked_add_signed(chrono::Duration::days(self.period_multiple as i64));
-                match working_dt {
-                    Some(dt) => Ok(dt),
-                    None => {
-                        return Err(ContractError::DayUpdateError("unknown error".to_string()));
-                    }
-                }
-            }
-            PeriodType::MONTHS => {
-                let working_month = new_dt.month() as u16 + self.period_multiple;
-                match working_month {
-                    2..=12 => Ok(NaiveDate::from_ymd(new_dt.year(), working_month as u32, 1)
-                        .and_hms(0, 0, 0)),
-                    13..=268 => {
-                        let year_increment: i32 = (working_month / 12u16) as i32;
-                        Ok(NaiveDate::from_ymd(
-                            new_dt.year() + year_increment,
-                            working_month as u32 % 12,
-                            1,
-                        )
-                        .and_hms(0, 0, 0))
-                    }
-                    _ => Err(ContractError::MonthUpdateError {}),
-                }
-            }
-        };
+        let new_reset = next_period_start(
+            &self.period_type,
+            self.period_multiple,
+            current_time.seconds(),
+        )?;
         self.reset_limits();
-        let dt = match new_dt {
-            Ok(dt) => dt,
-            Err(e) => return Err(ContractError::DayUpdateError(e.to_string())),
-        };
-        println!("Old reset date is {:?}", self.current_period_reset.clone());
-        println!("Resetting to {:?}", dt.clone().timestamp());
-        self.current_period_reset = dt.timestamp() as u64;
+        debug_attr!("old_period_reset", self.current_period_reset);
+        debug_attr!("new_period_reset", new_reset);
+        self.current_period_reset = new_reset;
         Ok(())
     }
 }
 
+/// When a period starting at `from` (seconds) ends: `period_multiple`
+/// days later, or the first of the month `period_multiple` months on.
+/// Used for hot wallet resets and subscription due dates.
+pub fn next_period_start(
+    period_type: &PeriodType,
+    period_multiple: u16,
+    from: u64,
+) -> Result<u64, ContractError> {
+    // a zero period would reset to now (or the past), not the future
+    if period_multiple == 0 {
+        return Err(ContractError::DayUpdateError(
+            "period multiple must be at least 1".to_string(),
+        ));
+    }
+    let new_dt = match NaiveDateTime::from_timestamp_opt(from as i64, 0u32) {
+        Some(dt) => dt,
+        None => {
+            return Err(ContractError::DayUpdateError(
+                "time out of range".to_string(),
+            ))
+        }
+    };
+    // how far ahead the new period starts depends on the
+    // period type and multiple
+    let new_dt: Result<NaiveDateTime, ContractError> = match period_type {
+        PeriodType::DAYS => {
+            let working_dt =
+                new_dt.checked_add_signed(chrono::Duration::days(period_multiple as i64));
+            match working_dt {
+                Some(dt) => Ok(dt),
+                None => {
+                    return Err(ContractError::DayUpdateError("unknown error".to_string()));
+                }
+            }
+        }
+        PeriodType::MONTHS => {
+            // count months from 0 so that December doesn't wrap to month 0
+            let working_month = new_dt.month0() as i32 + period_multiple as i32;
+            match NaiveDate::from_ymd_opt(
+                new_dt.year() + working_month / 12,
+                (working_month % 12) as u32 + 1,
+                1,
+            ) {
+                Some(date) => Ok(date.and_hms(0, 0, 0)),
+                None => Err(ContractError::MonthUpdateError {}),
+            }
+        }
+    };
+    let dt = match new_dt {
+        Ok(dt) => dt,
+        Err(e) => return Err(ContractError::DayUpdateError(e.to_string())),
+    };
+    Ok(dt.timestamp() as u64)
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct HotWalletsResponse {
     pub hot_wallets: Vec<HotWallet>,
 }
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SpendLimitStatusResponse {
+    pub wallet: String,
+    pub paused: bool,
+    pub limit: u64,           // USD, per period
+    pub limit_remaining: u64, // USD, with any due reset applied
+    pub period_end: Timestamp,
+    /// the remaining limit in each native asset the proxy can price
+    pub native_equivalents: Vec<SourcedCoin>,
+}
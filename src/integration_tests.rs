@@ -1,118 +1,152 @@
-use crate::msg::{AdminResponse, ExecuteMsg, InstantiateMsg};
-use anyhow::{anyhow, Result};
-use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, Empty, QueryRequest, StdError, Uint128, WasmMsg, WasmQuery,
-};
-use cw1::Cw1Contract;
-use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
-use derivative::Derivative;
-use serde::{de::DeserializeOwned, Serialize};
-
-#[allow(dead_code)]
-fn mock_app() -> App {
-    App::default()
+use cosmwasm_std::{coins, Uint128};
+
+use crate::constants::MAINNET_AXLUSDC_IBC;
+use crate::mock_pair::MockPair;
+use crate::pair_contract::PairMessageType;
+use crate::suite::{daily_hot_wallet, SuiteBuilder, SUITE_ADMIN, SUITE_DENOM};
+use crate::ContractError;
+
+const HOT_WALLET: &str = "hotcarl";
+const RECEIVER: &str = "diane";
+
+// 1 testtoken is worth 2 USDC
+fn pair(query_format: PairMessageType) -> MockPair {
+    MockPair {
+        query_format,
+        denom1: SUITE_DENOM.to_string(),
+        denom2: MAINNET_AXLUSDC_IBC.to_string(),
+        reserve1: Uint128::from(1_000_000_000_000u128),
+        reserve2: Uint128::from(2_000_000_000_000u128),
+    }
 }
 
-#[allow(dead_code)]
-fn contract_cw1() -> Box<dyn Contract<Empty>> {
-    let contract = ContractWrapper::new(
-        crate::contract::execute,
-        crate::contract::instantiate,
-        crate::contract::query,
+#[test]
+fn spends_are_valued_by_pair_reserves() {
+    let mut suite = SuiteBuilder::new()
+        .with_daily_hot_wallet(HOT_WALLET, 1_000_000)
+        .with_funds(&coins(10_000_000, SUITE_DENOM))
+        .with_pair(pair(PairMessageType::JunoType))
+        .build();
+
+    suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(100_000, SUITE_DENOM))
+        .unwrap();
+    assert_eq!(
+        suite.spend_limit_status(HOT_WALLET).limit_remaining,
+        800_001
     );
-    Box::new(contract)
+
+    // the same spend is now worth 20 USDC, over what's left
+    suite
+        .set_reserves(0, 1_000_000_000_000, 20_000_000_000_000)
+        .unwrap();
+    suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(100_000, SUITE_DENOM))
+        .unwrap_err();
+    assert_eq!(
+        suite.spend_limit_status(HOT_WALLET).limit_remaining,
+        800_001
+    );
+    suite.assert_native_balance(RECEIVER, SUITE_DENOM, 100_000);
 }
 
-#[derive(Derivative)]
-#[derivative(Debug)]
-pub struct Suite {
-    /// Application mock
-    #[allow(dead_code)]
-    #[derivative(Debug = "ignore")]
-    app: App,
-    /// Special account
-    pub owner: String,
-    /// ID of stored code for cw1 contract
-    cw1_id: u64,
+#[test]
+fn loop_pair_commission_counts_toward_value() {
+    // loop returns 99.7% of the swap, but its commission is counted too
+    let mut suite = SuiteBuilder::new()
+        .with_daily_hot_wallet(HOT_WALLET, 1_000_000)
+        .with_funds(&coins(10_000_000, SUITE_DENOM))
+        .with_pair(pair(PairMessageType::LoopType))
+        .build();
+
+    suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(100_000, SUITE_DENOM))
+        .unwrap();
+    assert_eq!(
+        suite.spend_limit_status(HOT_WALLET).limit_remaining,
+        800_001
+    );
 }
 
-impl Suite {
-    #[allow(dead_code)]
-    pub fn init() -> Result<Suite> {
-        let mut app = mock_app();
-        let owner = "owner".to_owned();
-        let cw1_id = app.store_code(contract_cw1());
+#[test]
+fn failed_price_query_blocks_spend() {
+    // the proxy expects a loop pair, but the contract answers junoswap queries
+    let mut suite = SuiteBuilder::new()
+        .with_daily_hot_wallet(HOT_WALLET, 1_000_000)
+        .with_funds(&coins(10_000_000, SUITE_DENOM))
+        .with_pair_queried_as(pair(PairMessageType::JunoType), PairMessageType::LoopType)
+        .build();
 
-        Ok(Suite { app, owner, cw1_id })
+    let err = suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(100_000, SUITE_DENOM))
+        .unwrap_err();
+    match err.downcast::<ContractError>().unwrap() {
+        ContractError::PriceCheckFailed(_, contract_addr, _) => {
+            assert_eq!(contract_addr, suite.pairs[0].contract_addr)
+        }
+        err => panic!("unexpected error {:?}", err),
     }
+    suite.assert_native_balance(RECEIVER, SUITE_DENOM, 0);
+}
 
-    #[allow(dead_code)]
-    pub fn instantiate_cw1_contract(
-        &mut self,
-        admin: String,
-        hot_wallets: Vec<crate::hot_wallet::HotWallet>,
-    ) -> Cw1Contract {
-        let contract = self
-            .app
-            .instantiate_contract(
-                self.cw1_id,
-                Addr::unchecked(self.owner.clone()),
-                &InstantiateMsg {
-                    admin,
-                    hot_wallets,
-                    uusd_fee_debt: Uint128::from(0u128),
-                    fee_lend_repay_wallet: "test_repay_address".to_string(),
-                    home_network: "local".to_string(),
-                    signers: [
-                        "testsigner1".to_string(),
-                        "testsigner2".to_string(),
-                        "testsigner3".to_string(),
-                    ]
-                    .to_vec(),
-                },
-                &[],
-                "Whitelist",
-                None,
-            )
-            .unwrap();
-        Cw1Contract(contract)
-    }
+#[test]
+fn hot_wallet_limit_resets_after_period() {
+    // the default pair prices a testtoken at 100 USDC micro units
+    let mut suite = SuiteBuilder::new()
+        .with_daily_hot_wallet(HOT_WALLET, 1_000_000)
+        .with_funds(&coins(100_000, SUITE_DENOM))
+        .build();
 
-    #[allow(dead_code)]
-    pub fn execute<M>(
-        &mut self,
-        sender_contract: Addr,
-        target_contract: &Addr,
-        msg: M,
-    ) -> Result<AppResponse>
-    where
-        M: Serialize + DeserializeOwned,
-    {
-        let execute: ExecuteMsg = ExecuteMsg::Execute {
-            msgs: vec![CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: target_contract.to_string(),
-                msg: to_binary(&msg)?,
-                funds: vec![],
-            })],
-        };
-        self.app
-            .execute_contract(
-                Addr::unchecked(self.owner.clone()),
-                sender_contract,
-                &execute,
-                &[],
-            )
-            .map_err(|err| anyhow!(err))
-    }
+    suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(9_000, SUITE_DENOM))
+        .unwrap();
+    assert_eq!(
+        suite.spend_limit_status(HOT_WALLET).limit_remaining,
+        100_001
+    );
+    suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(2_000, SUITE_DENOM))
+        .unwrap_err();
 
-    #[allow(dead_code)]
-    pub fn query<M>(&self, target_contract: Addr, msg: M) -> Result<AdminResponse, StdError>
-    where
-        M: Serialize + DeserializeOwned,
-    {
-        self.app.wrap().query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: target_contract.to_string(),
-            msg: to_binary(&msg).unwrap(),
-        }))
-    }
+    // a day on is still the same period, a second later it resets
+    suite.advance_days(1);
+    suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(2_000, SUITE_DENOM))
+        .unwrap_err();
+    suite.advance_seconds(1);
+    suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(2_000, SUITE_DENOM))
+        .unwrap();
+
+    suite.assert_native_balance(RECEIVER, SUITE_DENOM, 11_000);
+    let proxy = suite.proxy.to_string();
+    suite.assert_native_balance(&proxy, SUITE_DENOM, 89_000);
+}
+
+#[test]
+fn added_hot_wallet_and_admin_spend_funds() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds(&coins(100_000, SUITE_DENOM))
+        .with_cw20_funds("OBI", 1_000)
+        .build();
+
+    // no hot wallet yet
+    suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(1_000, SUITE_DENOM))
+        .unwrap_err();
+    suite
+        .add_hot_wallet(daily_hot_wallet(HOT_WALLET, 500_000))
+        .unwrap();
+    suite
+        .send_native(HOT_WALLET, RECEIVER, &coins(1_000, SUITE_DENOM))
+        .unwrap();
+    suite.assert_native_balance(RECEIVER, SUITE_DENOM, 1_000);
+
+    // admin isn't limited, and can move cw20s
+    suite
+        .transfer_cw20(SUITE_ADMIN, "OBI", RECEIVER, 400)
+        .unwrap();
+    suite.assert_cw20_balance("OBI", RECEIVER, 400);
+    let proxy = suite.proxy.to_string();
+    suite.assert_cw20_balance("OBI", &proxy, 600);
 }
@@ -1,28 +1,57 @@
 pub mod constants;
 pub mod contract;
+pub mod debug;
 pub mod error;
+pub mod events;
 pub mod hot_wallet;
 #[cfg(test)]
 mod integration_tests;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod mock_pair;
 pub mod msg;
+pub mod overlay;
 pub mod pair_contract;
 pub mod pair_contract_defaults;
+pub mod proposal;
+pub mod recovery;
+pub mod scheduled;
+pub mod session_key;
+pub mod signed;
 pub mod simulation;
 pub mod sourced_coin;
 pub mod sources;
 pub mod state;
 pub mod submsgs;
+pub mod subscription;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod suite;
 #[cfg(test)]
 pub mod tests_constants;
 #[cfg(test)]
 mod tests_contract;
 #[cfg(test)]
+mod tests_events;
+#[cfg(test)]
 pub mod tests_helpers;
 #[cfg(test)]
 mod tests_hot_wallet;
 #[cfg(test)]
+mod tests_overlay;
+#[cfg(test)]
 mod tests_pair_contract;
 #[cfg(test)]
+mod tests_proposal;
+#[cfg(test)]
+mod tests_recovery;
+#[cfg(test)]
+mod tests_scheduled;
+#[cfg(test)]
+mod tests_session_key;
+#[cfg(test)]
+mod tests_signed;
+#[cfg(test)]
 mod tests_state;
+#[cfg(test)]
+mod tests_subscription;
 
 pub use crate::error::ContractError;
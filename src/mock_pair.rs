@@ -0,0 +1,160 @@
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::pair_contract::PairMessageType;
+use crate::simulation::{
+    AssetInfo, DexQueryMsgFormatted, ReverseSimulationResponse, SimulationResponse,
+    Token1ForToken2PriceResponse, Token2ForToken1PriceResponse,
+};
+
+/// A constant product pair that answers price queries the way a Loop or
+/// JunoSwap pair would, so the proxy's real price queries can be tested
+/// against configurable reserves. Also its instantiate message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MockPair {
+    pub query_format: PairMessageType,
+    pub denom1: String,
+    pub denom2: String,
+    pub reserve1: Uint128,
+    pub reserve2: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MockPairExecuteMsg {
+    SetReserves {
+        reserve1: Uint128,
+        reserve2: Uint128,
+    },
+}
+
+const MOCK_PAIR: Item<MockPair> = Item::new("mock_pair");
+
+// Loop simulations take their commission out of the return amount
+const LOOP_COMMISSION_PERMILLE: u128 = 3;
+
+impl MockPair {
+    /// returns (offer reserve, ask reserve) when offering `denom`
+    fn reserves_offering(&self, denom: &str) -> StdResult<(Uint128, Uint128)> {
+        match denom {
+            val if val == self.denom1 => Ok((self.reserve1, self.reserve2)),
+            val if val == self.denom2 => Ok((self.reserve2, self.reserve1)),
+            _ => Err(StdError::generic_err(format!(
+                "denom {} not in this pair",
+                denom
+            ))),
+        }
+    }
+
+    fn native_denom(info: &AssetInfo) -> StdResult<String> {
+        match info {
+            AssetInfo::NativeToken { denom } => Ok(denom.clone()),
+            AssetInfo::Token { .. } => Err(StdError::generic_err("mock pair is native only")),
+        }
+    }
+
+    /// what is returned for `amount` of the offered asset
+    fn swap_return(offer_reserve: Uint128, ask_reserve: Uint128, amount: Uint128) -> Uint128 {
+        ask_reserve.multiply_ratio(amount, offer_reserve + amount)
+    }
+
+    /// what must be offered to get `amount` of the asked asset
+    fn swap_offer(
+        offer_reserve: Uint128,
+        ask_reserve: Uint128,
+        amount: Uint128,
+    ) -> StdResult<Uint128> {
+        if amount >= ask_reserve {
+            return Err(StdError::generic_err("not enough liquidity"));
+        }
+        Ok(offer_reserve.multiply_ratio(amount, ask_reserve - amount))
+    }
+
+    pub fn respond(&self, msg: &DexQueryMsgFormatted) -> StdResult<Binary> {
+        match (&self.query_format, msg) {
+            (PairMessageType::LoopType, DexQueryMsgFormatted::Simulation(msg)) => {
+                let (offer_reserve, ask_reserve) =
+                    self.reserves_offering(&Self::native_denom(&msg.offer_asset.info)?)?;
+                let gross = Self::swap_return(offer_reserve, ask_reserve, msg.offer_asset.amount);
+                let commission_amount = gross.multiply_ratio(LOOP_COMMISSION_PERMILLE, 1000u128);
+                to_binary(&SimulationResponse {
+                    commission_amount,
+                    return_amount: gross - commission_amount,
+                    spread_amount: ask_reserve
+                        .multiply_ratio(msg.offer_asset.amount, offer_reserve)
+                        - gross,
+                })
+            }
+            (PairMessageType::LoopType, DexQueryMsgFormatted::ReverseSimulation(msg)) => {
+                let (ask_reserve, offer_reserve) =
+                    self.reserves_offering(&Self::native_denom(&msg.ask_asset.info)?)?;
+                let offer_amount =
+                    Self::swap_offer(offer_reserve, ask_reserve, msg.ask_asset.amount)?;
+                to_binary(&ReverseSimulationResponse {
+                    commission_amount: Uint128::zero(),
+                    offer_amount,
+                    spread_amount: offer_amount
+                        - offer_reserve.multiply_ratio(msg.ask_asset.amount, ask_reserve),
+                })
+            }
+            (PairMessageType::JunoType, DexQueryMsgFormatted::Token1ForToken2Price(msg)) => {
+                to_binary(&Token1ForToken2PriceResponse {
+                    token2_amount: Self::swap_return(
+                        self.reserve1,
+                        self.reserve2,
+                        msg.token1_amount,
+                    ),
+                })
+            }
+            (PairMessageType::JunoType, DexQueryMsgFormatted::Token2ForToken1Price(msg)) => {
+                to_binary(&Token2ForToken1PriceResponse {
+                    token1_amount: Self::swap_return(
+                        self.reserve2,
+                        self.reserve1,
+                        msg.token2_amount,
+                    ),
+                })
+            }
+            _ => Err(StdError::generic_err(format!(
+                "{:?} pair can't answer {:?}",
+                self.query_format, msg
+            ))),
+        }
+    }
+}
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockPair,
+) -> StdResult<Response> {
+    MOCK_PAIR.save(deps.storage, &msg)?;
+    Ok(Response::new())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockPairExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        MockPairExecuteMsg::SetReserves { reserve1, reserve2 } => {
+            MOCK_PAIR.update(deps.storage, |mut pair| -> StdResult<_> {
+                pair.reserve1 = reserve1;
+                pair.reserve2 = reserve2;
+                Ok(pair)
+            })?;
+            Ok(Response::new())
+        }
+    }
+}
+
+pub fn query(deps: Deps, _env: Env, msg: DexQueryMsgFormatted) -> StdResult<Binary> {
+    MOCK_PAIR.load(deps.storage)?.respond(&msg)
+}
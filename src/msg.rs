@@ -1,9 +1,15 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CosmosMsg, Uint128};
+use cosmwasm_std::{Binary, CosmosMsg, Timestamp, Uint128};
 
 use crate::hot_wallet::HotWallet;
+use crate::session_key::SessionKey;
+use crate::signed::SignedPayload;
+use crate::sourced_coin::SourcedCoin;
+use crate::state::{FeeDebtRecord, FeeRepayPolicy, SpendRecord};
+use crate::submsgs::SubmsgType;
+use crate::subscription::Subscription;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -22,23 +28,139 @@ pub enum ExecuteMsg {
     /// contract's address as sender. Every implementation has it's own logic to
     /// determine in
     Execute { msgs: Vec<CosmosMsg> },
-    /// Might still have spend limit etc affects, but avoids attaching
-    /// any messages. Attaches attributes as normal. For debugging purposes –
-    /// others should use query
+    /// Runs Execute without attaching any messages or keeping any state
+    /// changes, so spend limits and fee debt are untouched. Attaches
+    /// attributes as normal. For debugging purposes – others should use
+    /// the SimulateExecute query
     SimExecute { msgs: Vec<CosmosMsg> },
     /// Proposes a new admin for the proxy contract – must be called by the existing admin
     ProposeUpdateAdmin { new_admin: String },
-    /// Confirms a proposed admin - must be called by the new admin.
+    /// Confirms a proposed admin - must be called by the new admin, once the
+    /// admin transfer delay has passed. `signers` replace the stored signers.
     /// This is to prevent accidentally transitioning to an uncontrolled address.
     ConfirmUpdateAdmin { signers: Vec<String> },
-    /// Cancels a proposed admin - must be called by current admin.
-    /// This can be used to cancel during a waiting period.
+    /// Cancels a proposed admin - must be called by current admin (or by the
+    /// proposed admin, to decline). This can be used to cancel during the
+    /// waiting period.
     CancelUpdateAdmin {},
+    /// Sets how long a proposed admin must wait before confirming – must be
+    /// called by admin. Applies to proposals made after this is set.
+    SetAdminTransferDelay { delay_seconds: u64 },
+    /// Sets the guardians who can recover the admin role, how many of them
+    /// must approve, and how long the admin has to veto – must be called by
+    /// admin. Clears any proposed recovery.
+    SetGuardians {
+        guardians: Vec<String>,
+        threshold: u32,
+        recovery_delay: u64,
+    },
+    /// Proposes a new admin on behalf of the guardians – must be called by a
+    /// guardian, whose approval is counted. While another recovery is pending,
+    /// this backs a rival instead, which replaces the pending recovery once a
+    /// threshold of guardians backs it.
+    ProposeRecovery { new_admin: String },
+    /// Approves the proposed recovery – must be called by a guardian.
+    ApproveRecovery {},
+    /// Makes the proposed admin the admin, once enough guardians have approved
+    /// and the recovery delay has passed, but before it expires. Can be called
+    /// by anyone.
+    ExecuteRecovery {},
+    /// Vetoes the proposed recovery – must be called by admin.
+    VetoRecovery {},
+    /// Sets who can claim admin once admin has sent nothing for
+    /// inactivity_seconds, or clears it with None – must be called by admin.
+    SetHeir {
+        heir: Option<String>,
+        inactivity_seconds: u64,
+    },
+    /// Shows admin is still active, and cancels any claim by the heir –
+    /// must be called by admin. Any other admin transaction does the same.
+    Heartbeat {},
+    /// Proposes the heir as admin once admin has been inactive long enough
+    /// – must be called by the heir. After the grace period (the admin
+    /// transfer delay, or a week if that is shorter), the heir confirms
+    /// with ConfirmUpdateAdmin. Until then, any admin transaction cancels
+    /// the claim.
+    ClaimInheritance {},
+    /// Disables all non-admin execution, such as if a hot wallet key leaks.
+    /// Can be called by admin, any guardian, or any hot wallet.
+    Freeze {},
+    /// Lifts a freeze – must be called by admin.
+    Unfreeze {},
+    /// Replaces the stored signers behind the admin – must be called by admin.
+    UpdateSigners { signers: Vec<String> },
+    /// Sets how many signers or hot wallets must approve a proposal, at least
+    /// 2 – must be called by admin.
+    SetProposalThreshold { threshold: u32 },
+    /// Proposes spend messages too large for a hot wallet's spend limit – must
+    /// be called by a signer or hot wallet, whose approval is counted.
+    ProposeExecute {
+        msgs: Vec<CosmosMsg>,
+        expires: Timestamp,
+    },
+    /// Approves a proposal – must be called by a signer or hot wallet.
+    ApproveProposal { id: u64 },
+    /// Executes a proposal once it has enough approvals, if not expired.
+    /// Can be called by anyone.
+    ExecuteProposal { id: u64 },
+    /// Sets the secp256k1 public key the calling hot wallet signs
+    /// payloads with for ExecuteSigned.
+    SetSigningKey { pubkey: Binary },
+    /// Submits a hot wallet's off-chain-signed batch on its behalf. The
+    /// messages are checked against the hot wallet's spend limits, as if
+    /// it had sent Execute. `pubkey` must match its signing key, and
+    /// `signature` is over the sha256 of the payload's JSON.
+    ExecuteSigned {
+        payload: SignedPayload,
+        pubkey: Binary,
+        signature: Binary,
+    },
+    /// Adds a session key, which can only execute messages on one contract
+    /// until it expires – must be called by admin.
+    CreateSessionKey { session_key: SessionKey },
+    /// Removes a session key – must be called by admin or the session key.
+    RevokeSessionKey { address: String },
+    /// Registers a recurring payment from the proxy – must be called by admin.
+    AddSubscription { subscription: Subscription },
+    /// Removes a subscription – must be called by admin.
+    CancelSubscription { id: u64 },
+    /// Pays each of these subscriptions that is due for one period, and
+    /// moves its next due date on a period. Can be called by anyone.
+    TriggerPayments { ids: Vec<u64> },
+    /// Queues a bank send, or cw20 transfer or send, to go out once
+    /// execute_after has passed – must be called by admin.
+    ScheduleTransfer {
+        msg: CosmosMsg,
+        execute_after: Timestamp,
+    },
+    /// Cancels a scheduled transfer before it matures – must be called by admin.
+    CancelScheduledTransfer { id: u64 },
+    /// Sends a scheduled transfer once matured. Can be called by anyone.
+    ExecuteScheduledTransfer { id: u64 },
     /// Adds a spend-limited wallet, which can call cw20 Transfer/Send and BankMsg
     /// transactions if within the known recurring spend limit.
     AddHotWallet { new_hot_wallet: HotWallet },
     /// Removes an active spend-limited wallet.
     RmHotWallet { doomed_hot_wallet: String },
+    /// Pauses a hot wallet without touching its spend limits – must be
+    /// called by admin.
+    PauseHotWallet { address: String },
+    /// Unpauses a hot wallet – must be called by admin.
+    UnpauseHotWallet { address: String },
+    /// Pauses the calling hot wallet, such as if its device may be
+    /// compromised. Only admin can unpause it.
+    RevokeSelf {},
+    /// Repays the outstanding fee debt in full using `asset` (a denom), without
+    /// counting against spend limits. Can be called by admin or hot wallets.
+    RepayFeeDebt { asset: String },
+    /// Records newly sponsored fees as debt. Must be called by the fee lender,
+    /// and total debt cannot go over the admin-set maximum.
+    AddFeeDebt { amount: Uint128, memo: String },
+    /// Sets the maximum outstanding fee debt the lender can add up to – must be
+    /// called by admin
+    SetMaxFeeDebt { max_fee_debt: Uint128 },
+    /// Sets when fee debt must be repaid – must be called by admin
+    SetFeeRepayPolicy { policy: FeeRepayPolicy },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -49,19 +171,80 @@ pub enum QueryMsg {
     /// Shows pending admin (subject to becoming new admin when
     /// ConfirmUpdateAdmin is called successfully)
     Pending {},
+    /// Shows the proposed admin, if any, with when it was proposed and
+    /// when it can be confirmed
+    PendingAdmin {},
     /// Checks permissions of the caller on this proxy.
     /// If CanExecute returns true then a call to `Execute` with the same message,
-    /// before any further state changes, should also succeed.
-    /// TODO: support can_spend for hot wallets in this check.
+    /// before any further state changes, should also succeed. Hot wallets
+    /// get the same classification and spend limit checks as `Execute`.
     CanExecute { sender: String, msg: CosmosMsg },
     /// Gets an array of all the active HotWallets for this proxy.
     HotWallets {},
+    /// Shows a hot wallet's effective remaining limit, counting a reset
+    /// that is due but not yet applied, and when the period ends.
+    SpendLimitStatus { wallet: String },
     /// Returns true if address 1) is admin, 2) is hot wallet and msg is spendable
-    /// by hot wallet, or 3) is one of approved cw20s (no funds attached tho)
+    /// by hot wallet, or 3) is one of approved cw20s (no funds attached tho).
+    /// If not, gives a reason when there is one, such as a freeze.
     CanSpend {
         sender: String,
         msgs: Vec<CosmosMsg>,
     },
+    /// Simulates executing msgs as sender without changing anything, and
+    /// gives a verdict for each message: its type, USD value, any fee
+    /// repayment attached, the limit left after it, and the error if rejected.
+    /// Messages after a rejected one are checked as if it had been left out.
+    SimulateExecute {
+        sender: String,
+        msgs: Vec<CosmosMsg>,
+    },
+    /// Shows the outstanding fee debt, who it is owed to, and the quote
+    /// (in the home network's native asset) that repayment would use.
+    FeeDebt {},
+    /// Shows the admin and the signers stored for it.
+    Signers {},
+    /// Shows a proposal and whether it can be executed now.
+    Proposal { id: u64 },
+    /// Lists proposals, oldest first.
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Shows the guardians and their recovery settings.
+    Guardians {},
+    /// Shows the proposed recovery, if any, and whether it can be executed now.
+    RecoveryStatus {},
+    /// Shows the heir, if any, and whether the inheritance can be claimed now.
+    Heir {},
+    /// Shows the next nonce a hot wallet's signed payload must use, and its
+    /// signing key if set.
+    SignedNonce { wallet: String },
+    /// Lists session keys, including expired ones that haven't been revoked.
+    SessionKeys {},
+    /// Lists scheduled transfers that haven't been sent or cancelled,
+    /// oldest first.
+    ScheduledTransfers {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists subscriptions with their next due dates, oldest first.
+    Subscriptions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists every loan added to the fee debt, oldest first.
+    FeeDebtHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists a hot wallet's recent spends, oldest first. Records older
+    /// than 90 days are pruned.
+    SpendHistory {
+        wallet: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -73,9 +256,62 @@ pub struct AdminResponse {
     pub admin: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PendingAdminResponse {
+    pub pending: Option<String>,
+    pub proposed_at: Option<Timestamp>,
+    pub executable_at: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SignersResponse {
+    pub admin: String,
+    pub signers: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct CanSpendResponse {
     pub can_spend: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SimulateExecuteResponse {
+    /// true if the whole batch would go through
+    pub can_execute: bool,
+    /// fee repayment attached ahead of the messages, if any
+    pub fee_repay: Option<SourcedCoin>,
+    /// error that rejects the whole batch, such as a freeze
+    pub error: Option<String>,
+    pub msgs: Vec<MsgSimulation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MsgSimulation {
+    pub msg_type: SubmsgType,
+    pub usd_value: Option<SourcedCoin>,
+    pub fee_repay: Option<SourcedCoin>,
+    pub limit_remaining: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FeeDebtResponse {
+    pub uusd_fee_debt: Uint128,
+    pub max_fee_debt: Option<Uint128>,
+    pub fee_repay_policy: FeeRepayPolicy,
+    pub fee_lend_repay_wallet: String,
+    pub repay_quote: Option<SourcedCoin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct FeeDebtHistoryResponse {
+    pub records: Vec<(u64, FeeDebtRecord)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SpendHistoryResponse {
+    pub records: Vec<(u64, SpendRecord)>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
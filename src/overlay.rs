@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use cosmwasm_std::{Order, Record, Storage};
+
+/// Storage that reads through to `base` but keeps every write to itself,
+/// so that they can be thrown away. Lets simulations run the full execute
+/// pipeline without changing anything.
+pub struct OverlayStorage<'a> {
+    base: &'a dyn Storage,
+    // None marks a key removed in the overlay
+    writes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl<'a> OverlayStorage<'a> {
+    pub fn new(base: &'a dyn Storage) -> Self {
+        OverlayStorage {
+            base,
+            writes: BTreeMap::new(),
+        }
+    }
+
+    /// Ends the overlay, keeping its writes so they can be applied to a
+    /// storage once `base` is no longer borrowed.
+    pub fn into_writes(self) -> OverlayWrites {
+        OverlayWrites(self.writes)
+    }
+}
+
+/// Writes taken out of an `OverlayStorage`.
+pub struct OverlayWrites(BTreeMap<Vec<u8>, Option<Vec<u8>>>);
+
+impl OverlayWrites {
+    pub fn apply_to(self, storage: &mut dyn Storage) {
+        for (key, value) in self.0 {
+            match value {
+                Some(value) => storage.set(&key, &value),
+                None => storage.remove(&key),
+            }
+        }
+    }
+}
+
+impl Storage for OverlayStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.writes.get(key) {
+            Some(value) => value.clone(),
+            None => self.base.get(key),
+        }
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        if let (Some(start), Some(end)) = (start, end) {
+            if start >= end {
+                return Box::new(std::iter::empty());
+            }
+        }
+        // merged eagerly, since simulations only touch a few keys
+        let mut merged: BTreeMap<Vec<u8>, Vec<u8>> =
+            self.base.range(start, end, Order::Ascending).collect();
+        let lower = start.map_or(Bound::Unbounded, |start| Bound::Included(start.to_vec()));
+        let upper = end.map_or(Bound::Unbounded, |end| Bound::Excluded(end.to_vec()));
+        for (key, value) in self.writes.range((lower, upper)) {
+            match value {
+                Some(value) => merged.insert(key.clone(), value.clone()),
+                None => merged.remove(key),
+            };
+        }
+        let records: Vec<Record> = merged.into_iter().collect();
+        match order {
+            Order::Ascending => Box::new(records.into_iter()),
+            Order::Descending => Box::new(records.into_iter().rev()),
+        }
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.writes.insert(key.to_vec(), Some(value.to_vec()));
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.writes.insert(key.to_vec(), None);
+    }
+}
@@ -2,11 +2,11 @@ use cosmwasm_std::{to_binary, Coin, Deps, QueryRequest, StdError, Uint128, WasmQ
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[cfg(test)]
-use crate::tests_constants::get_test_sourced_coin;
 use crate::{
+    debug::debug_attr,
     simulation::{DexQueryMsg, Token1ForToken2PriceResponse, Token2ForToken1PriceResponse},
     simulation::{DexQueryMsgFormatted, DexQueryMsgType, FormatQueryMsg, Tally},
+    simulation::{ReverseSimulationResponse, SimulationResponse},
     sourced_coin::SourcedCoin,
     sources::{Source, Sources},
     ContractError,
@@ -31,8 +31,6 @@ impl PairContract {
         Ok((self.denom1.clone(), self.denom2.clone()))
     }
 
-    #[allow(unreachable_code)]
-    #[allow(unused_variables)]
     pub fn query_contract(
         self,
         deps: Deps,
@@ -49,26 +47,20 @@ impl PairContract {
             flip_assets = !flip_assets;
         }
         let query_msg = self.clone().create_query_msg(amount, flip_assets)?;
-        #[cfg(test)]
-        println!(
-            "Bypassing query message on contract {}: {:?}, and bools are reverse: {}, reverse_message_type: {}, amount_is_target: {}",
-            self.contract_addr, query_msg, reverse, reverse_message_type, amount_is_target
-        );
-        println!("flip_assets is {}", flip_assets);
-        #[cfg(test)]
-        {
-            let test_denom1 = match flip_assets {
-                true => self.denom2,
-                false => self.denom1,
-            };
-            return get_test_sourced_coin((test_denom1, query_msg.1), amount, reverse);
-        }
-        let query_result: Result<SourcedCoin, ContractError>;
-        match flip_assets {
-            false => {
+        debug_attr!("pair", self.contract_addr);
+        debug_attr!("flip_assets", flip_assets);
+        // each query type has its own response shape
+        match query_msg.0 {
+            DexQueryMsgFormatted::Simulation(_) => {
+                self.process_query::<SimulationResponse>(deps, &query_msg.0, query_msg.1)
+            }
+            DexQueryMsgFormatted::ReverseSimulation(_) => {
+                self.process_query::<ReverseSimulationResponse>(deps, &query_msg.0, query_msg.1)
+            }
+            DexQueryMsgFormatted::Token1ForToken2Price(_) => {
                 self.process_query::<Token1ForToken2PriceResponse>(deps, &query_msg.0, query_msg.1)
             }
-            true => {
+            DexQueryMsgFormatted::Token2ForToken1Price(_) => {
                 self.process_query::<Token2ForToken1PriceResponse>(deps, &query_msg.0, query_msg.1)
             }
         }
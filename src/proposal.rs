@@ -0,0 +1,69 @@
+use cosmwasm_std::{Addr, Coin, CosmosMsg, Timestamp};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::submsgs::SubmsgType;
+use crate::ContractError;
+
+/// A spend too large for a hot wallet alone, which goes out once
+/// enough signers or hot wallets approve it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub msgs: Vec<CosmosMsg>,
+    pub msg_types: Vec<SubmsgType>,
+    pub spend: Vec<Coin>,
+    pub approvals: Vec<Addr>,
+    pub expires: Timestamp,
+    pub executed: bool,
+}
+
+impl Proposal {
+    pub fn is_expired(&self, current_time: Timestamp) -> bool {
+        current_time >= self.expires
+    }
+
+    pub fn approve(&mut self, approver: Addr) {
+        if !self.approvals.contains(&approver) {
+            self.approvals.push(approver);
+        }
+    }
+
+    /// errors if the proposal can no longer be approved or executed
+    pub fn assert_is_open(&self, current_time: Timestamp) -> Result<(), ContractError> {
+        if self.executed {
+            Err(ContractError::ProposalAlreadyExecuted {})
+        } else if self.is_expired(current_time) {
+            Err(ContractError::ProposalExpired {})
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn assert_is_executable(
+        &self,
+        threshold: u32,
+        current_time: Timestamp,
+    ) -> Result<(), ContractError> {
+        self.assert_is_open(current_time)?;
+        if (self.approvals.len() as u32) < threshold {
+            return Err(ContractError::ProposalNotApproved(
+                self.approvals.len() as u32,
+                threshold,
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub proposal: Proposal,
+    pub executable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
@@ -0,0 +1,170 @@
+use cosmwasm_std::{Addr, StdError, StdResult, Timestamp};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::RECOVERY_EXECUTION_WINDOW;
+use crate::ContractError;
+
+/// Guardians can together replace the admin if the admin's keys are lost.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct GuardianSet {
+    pub guardians: Vec<Addr>,
+    pub threshold: u32,
+    pub recovery_delay: u64, //seconds
+}
+
+impl GuardianSet {
+    pub fn is_guardian(&self, addr: String) -> bool {
+        self.guardians.iter().any(|guardian| guardian == &addr)
+    }
+
+    pub fn assert_is_valid(&self) -> Result<(), ContractError> {
+        if self.threshold == 0 || self.threshold as usize > self.guardians.len() {
+            return Err(ContractError::InvalidGuardianThreshold {});
+        }
+        Ok(())
+    }
+}
+
+/// A proposal by guardians to replace the admin. The current admin
+/// can veto it until it is executed. It expires if not executed in
+/// time, and a threshold of guardians backing a different new admin
+/// replaces it, so one guardian cannot hold recovery up.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct Recovery {
+    pub new_admin: Addr,
+    pub approvals: Vec<Addr>,
+    pub proposed_at: Timestamp,
+    pub executable_at: Timestamp,
+    pub expires_at: Timestamp,
+    pub rivals: Vec<RivalRecovery>,
+}
+
+/// A guardian's backing for a different new admin than the pending
+/// recovery's.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct RivalRecovery {
+    pub guardian: Addr,
+    pub new_admin: Addr,
+}
+
+impl Recovery {
+    pub fn new(
+        new_admin: Addr,
+        approvals: Vec<Addr>,
+        guardian_set: &GuardianSet,
+        current_time: Timestamp,
+    ) -> Self {
+        let executable_at = current_time.plus_seconds(guardian_set.recovery_delay);
+        Recovery {
+            new_admin,
+            approvals,
+            proposed_at: current_time,
+            executable_at,
+            expires_at: executable_at.plus_seconds(RECOVERY_EXECUTION_WINDOW),
+            rivals: vec![],
+        }
+    }
+
+    pub fn is_expired(&self, current_time: Timestamp) -> bool {
+        current_time >= self.expires_at
+    }
+
+    pub fn approve(&mut self, guardian: Addr) {
+        self.rivals.retain(|rival| rival.guardian != guardian);
+        if !self.approvals.contains(&guardian) {
+            self.approvals.push(guardian);
+        }
+    }
+
+    /// Moves `guardian`'s backing to `new_admin` and returns every
+    /// guardian now backing it.
+    pub fn back_rival(&mut self, guardian: Addr, new_admin: Addr) -> Vec<Addr> {
+        self.approvals.retain(|approval| approval != &guardian);
+        self.rivals.retain(|rival| rival.guardian != guardian);
+        self.rivals.push(RivalRecovery {
+            guardian,
+            new_admin: new_admin.clone(),
+        });
+        self.rivals
+            .iter()
+            .filter(|rival| rival.new_admin == new_admin)
+            .map(|rival| rival.guardian.clone())
+            .collect()
+    }
+
+    pub fn assert_is_executable(
+        &self,
+        guardian_set: &GuardianSet,
+        current_time: Timestamp,
+    ) -> Result<(), ContractError> {
+        if (self.approvals.len() as u32) < guardian_set.threshold {
+            return Err(ContractError::RecoveryNotApproved(
+                self.approvals.len() as u32,
+                guardian_set.threshold,
+            ));
+        }
+        if current_time < self.executable_at {
+            return Err(ContractError::RecoveryTimelocked(
+                self.executable_at.seconds(),
+            ));
+        }
+        if self.is_expired(current_time) {
+            return Err(ContractError::RecoveryExpired(self.expires_at.seconds()));
+        }
+        Ok(())
+    }
+}
+
+/// A dead-man switch: once admin has sent nothing for
+/// `inactivity_seconds`, the heir can claim admin. The claim becomes a
+/// pending admin transfer, which any admin transaction cancels until
+/// the heir confirms it.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct Heir {
+    pub heir: Addr,
+    pub inactivity_seconds: u64,
+    pub last_admin_activity: Timestamp,
+    /// true from a claim until admin is next active
+    pub claim_pending: bool,
+}
+
+impl Heir {
+    pub fn assert_is_valid(&self) -> StdResult<()> {
+        if self.inactivity_seconds == 0 {
+            return Err(StdError::GenericErr {
+                msg: "Heir inactivity period must be at least 1 second.".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// seconds, saturating rather than overflowing for long periods
+    pub fn claimable_at(&self) -> u64 {
+        self.last_admin_activity
+            .seconds()
+            .saturating_add(self.inactivity_seconds)
+    }
+
+    pub fn is_claimable(&self, current_time: Timestamp) -> bool {
+        current_time.seconds() >= self.claimable_at()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct GuardiansResponse {
+    pub guardians: Option<GuardianSet>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct RecoveryStatusResponse {
+    pub recovery: Option<Recovery>,
+    pub threshold: u32,
+    pub executable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct HeirResponse {
+    pub heir: Option<Heir>,
+    pub claimable: bool,
+}
@@ -0,0 +1,38 @@
+use cosmwasm_std::{Coin, CosmosMsg, Timestamp};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::submsgs::{SubmsgType, WasmmsgType};
+use crate::ContractError;
+
+/// A single payment queued by admin. Admin can cancel it until
+/// `execute_after`, and from then on anyone can send it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduledTransfer {
+    pub msg: CosmosMsg,
+    pub msg_type: SubmsgType,
+    pub spend: Vec<Coin>,
+    pub execute_after: Timestamp,
+}
+
+impl ScheduledTransfer {
+    pub fn is_matured(&self, current_time: Timestamp) -> bool {
+        current_time >= self.execute_after
+    }
+
+    /// errors if msg_type is not a bank send, or a cw20 transfer or send
+    pub fn assert_is_transfer(msg_type: &SubmsgType) -> Result<(), ContractError> {
+        match msg_type {
+            SubmsgType::BankSend
+            | SubmsgType::ExecuteWasm(WasmmsgType::Cw20Transfer)
+            | SubmsgType::ExecuteWasm(WasmmsgType::Cw20Send) => Ok(()),
+            SubmsgType::Unknown => Err(ContractError::BadMessageType("unknown".to_string())),
+            _ => Err(ContractError::OnlyTransferSendAllowed {}),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduledTransfersResponse {
+    pub transfers: Vec<(u64, ScheduledTransfer)>,
+}
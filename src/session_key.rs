@@ -0,0 +1,163 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use cosmwasm_std::{
+    from_slice, Binary, Coin, CosmosMsg, StdError, StdResult, Timestamp, Uint128, WasmMsg,
+};
+use schemars::JsonSchema;
+use serde::de::{self, IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::ContractError;
+
+/// A throwaway key, e.g. for a game or marketplace, that can only
+/// execute messages on one contract, for up to `usd_cap` worth of
+/// attached funds, until it expires. Unlike a hot wallet, its cap
+/// never resets.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SessionKey {
+    pub address: String,
+    pub contract: String,
+    /// names of the execute messages allowed, e.g. "buy_nft" for
+    /// {"buy_nft": {..}}. Empty allows any message
+    pub allowed_msgs: Vec<String>,
+    pub usd_cap: u64,
+    pub usd_spent: u64,
+    pub expires: Timestamp,
+}
+
+impl SessionKey {
+    pub fn is_expired(&self, current_time: Timestamp) -> bool {
+        current_time >= self.expires
+    }
+
+    pub fn assert_is_valid(&self, current_time: Timestamp) -> StdResult<()> {
+        if self.is_expired(current_time) {
+            return Err(StdError::GenericErr {
+                msg: "Session key must expire in the future.".to_string(),
+            });
+        }
+        if self.allowed_msgs.iter().any(|name| name.is_empty()) {
+            return Err(StdError::GenericErr {
+                msg: "Session key message names cannot be empty.".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// errors unless msg executes an allowed message on the session
+    /// key's contract, and returns the funds attached
+    pub fn check_msg(&self, msg: &CosmosMsg) -> Result<Vec<Coin>, ContractError> {
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) if contract_addr == &self.contract => {
+                let name = msg_name(msg).unwrap_or_default();
+                if self.allowed_msgs.is_empty() || self.allowed_msgs.contains(&name) {
+                    Ok(funds.clone())
+                } else {
+                    Err(ContractError::SessionKeyMsgNotAllowed(name))
+                }
+            }
+            _ => Err(ContractError::SessionKeyWrongContract(
+                self.contract.clone(),
+            )),
+        }
+    }
+
+    pub fn spend(&mut self, usd_amount: Uint128) -> Result<(), ContractError> {
+        let over_cap = |usd_spent: String| {
+            ContractError::SessionKeyOverCap(usd_spent, self.usd_cap.to_string())
+        };
+        // anything past u64 is over any cap
+        let usd_amount =
+            u64::try_from(usd_amount.u128()).map_err(|_| over_cap(usd_amount.to_string()))?;
+        let usd_spent = self.usd_spent.saturating_add(usd_amount);
+        if usd_spent > self.usd_cap {
+            return Err(over_cap(usd_spent.to_string()));
+        }
+        self.usd_spent = usd_spent;
+        Ok(())
+    }
+}
+
+/// the name of an execute message: the single key of its json object,
+/// e.g. "buy_nft" for {"buy_nft": {..}}
+fn msg_name(msg: &Binary) -> Option<String> {
+    from_slice::<MsgName>(msg.as_slice())
+        .ok()
+        .map(|msg_name| msg_name.0)
+}
+
+struct MsgName(String);
+
+// serde-json-wasm can't deserialize into a map type such as BTreeMap,
+// so the object's keys are walked by hand. Keys are read through
+// MsgKey, as it only reads keys with deserialize_str
+impl<'de> Deserialize<'de> for MsgName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("MsgName", &[], MsgNameVisitor)
+    }
+}
+
+struct MsgNameVisitor;
+
+impl<'de> Visitor<'de> for MsgNameVisitor {
+    type Value = MsgName;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object with a single key")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<MsgName, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let name: MsgKey = map
+            .next_key()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        map.next_value::<IgnoredAny>()?;
+        if map.next_key::<MsgKey>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Ok(MsgName(name.0))
+    }
+}
+
+struct MsgKey(String);
+
+impl<'de> Deserialize<'de> for MsgKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(MsgKeyVisitor)
+    }
+}
+
+struct MsgKeyVisitor;
+
+impl<'de> Visitor<'de> for MsgKeyVisitor {
+    type Value = MsgKey;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object key")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<MsgKey, E>
+    where
+        E: de::Error,
+    {
+        Ok(MsgKey(value.to_string()))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SessionKeysResponse {
+    pub session_keys: Vec<SessionKey>,
+}
@@ -0,0 +1,53 @@
+use cosmwasm_std::{to_vec, Binary, CosmosMsg, Env, StdResult, Timestamp, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::ContractError;
+
+/// A batch of messages a hot wallet signs off-chain, for a relayer to
+/// submit with ExecuteSigned. `proxy` and `chain_id` keep a signature
+/// from being replayed against another proxy or chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SignedPayload {
+    pub wallet: String,
+    pub proxy: String,
+    pub chain_id: String,
+    pub nonce: u64,
+    pub expires: Timestamp,
+    pub msgs: Vec<CosmosMsg>,
+    /// Relayer's gas cost in uusd, added to the fee debt. A payload with
+    /// a fee can only be relayed by the fee lender, and is rejected with
+    /// CallerIsNotFeeLender if anyone else submits it
+    pub relayer_fee: Option<Uint128>,
+}
+
+impl SignedPayload {
+    /// sha256 of the payload's JSON, which is what the hot wallet signs
+    pub fn sign_bytes(&self) -> StdResult<Vec<u8>> {
+        Ok(Sha256::digest(&to_vec(self)?).to_vec())
+    }
+
+    /// errors if the payload is for another proxy or chain, is stale, or
+    /// is out of order
+    pub fn assert_is_valid(&self, env: &Env, next_nonce: u64) -> Result<(), ContractError> {
+        if self.proxy != env.contract.address || self.chain_id != env.block.chain_id {
+            Err(ContractError::WrongSignedPayloadTarget {})
+        } else if env.block.time >= self.expires {
+            Err(ContractError::SignedPayloadExpired {})
+        } else if self.nonce != next_nonce {
+            Err(ContractError::BadNonce(next_nonce, self.nonce))
+        } else if self.msgs.is_empty() {
+            Err(ContractError::UninitializedMessage {})
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SignedNonceResponse {
+    pub wallet: String,
+    pub next_nonce: u64,
+    pub pubkey: Option<Binary>,
+}
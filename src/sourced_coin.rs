@@ -1,4 +1,4 @@
-use cosmwasm_std::{Attribute, Coin, Deps, Uint128};
+use cosmwasm_std::{Coin, Deps, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -16,20 +16,6 @@ pub struct SourcedCoin {
 }
 
 impl SourcedCoin {
-    pub fn sources_as_attributes(&self) -> Vec<Attribute> {
-        let mut attributes: Vec<Attribute> = vec![];
-        for n in 0..self.wrapped_sources.sources.len() {
-            attributes.push(Attribute {
-                key: format!(
-                    "query to contract {}",
-                    self.wrapped_sources.sources[n].contract_addr.clone()
-                ),
-                value: self.wrapped_sources.sources[n].query_msg.clone(),
-            })
-        }
-        attributes
-    }
-
     /// reverse is true if we have a target USDC amount (for fees)
     /// false if we're converting without a target (for spend limits)
     pub fn get_converted_to_usdc(
@@ -1,4 +1,3 @@
-use cosmwasm_std::Attribute;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -26,12 +25,4 @@ impl Sources {
             });
         }
     }
-
-    pub fn to_attributes(&self) -> Vec<Attribute> {
-        let mut attributes: Vec<Attribute> = vec![];
-        for source in self.sources.clone() {
-            attributes.push(Attribute::new(source.contract_addr, source.query_msg));
-        }
-        attributes
-    }
 }
@@ -1,17 +1,22 @@
 //use cw_multi_test::Contract;
-use cosmwasm_std::{Addr, Coin, Deps, StdError, StdResult, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Deps, StdError, StdResult, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
-use crate::constants::{MAINNET_ID, TESTNET_ID};
+use crate::constants::{MAINNET_DENOM, MAINNET_ID, TESTNET_DENOM, TESTNET_ID};
 use crate::hot_wallet::HotWallet;
 use crate::pair_contract::PairContract;
 use crate::pair_contract_defaults::{
     get_local_pair_contracts, get_mainnet_pair_contracts, get_testnet_pair_contracts,
 };
+use crate::proposal::Proposal;
+use crate::recovery::{GuardianSet, Heir, Recovery};
+use crate::scheduled::ScheduledTransfer;
+use crate::session_key::SessionKey;
 use crate::sourced_coin::SourcedCoin;
+use crate::subscription::Subscription;
 use crate::ContractError;
 
 use crate::sources::{Source, Sources};
@@ -32,12 +37,40 @@ pub fn get_admin_sourced_coin() -> SourcedCoin {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeRepayPolicy {
+    /// Debt is repaid out of the first spend that can cover it. Other
+    /// messages are not held up by the debt.
+    RepayFirstSpend,
+    /// Debt is repaid in the home network's native asset on any transaction,
+    /// whatever its messages.
+    RepayOnAnyTx,
+    /// Debt need not be repaid until the deadline. After that, hot wallets
+    /// are frozen until repayment, and admin transactions repay as with
+    /// RepayOnAnyTx.
+    RepayByDeadline { deadline: Timestamp },
+}
+
+/// When a proposed admin was proposed, and when they can confirm
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct AdminTransferWindow {
+    pub proposed_at: Timestamp,
+    pub executable_at: Timestamp,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct State {
     pub admin: Addr,
     pub pending: Addr,
+    pub admin_transfer_delay: Option<u64>, // seconds; None is 0
+    pub admin_transfer_window: Option<AdminTransferWindow>,
+    pub frozen_by: Option<Addr>, // None is not frozen
     pub hot_wallets: Vec<HotWallet>,
-    pub uusd_fee_debt: Uint128, // waiting to pay back fees
+    pub uusd_fee_debt: Uint128,        // waiting to pay back fees
+    pub max_fee_debt: Option<Uint128>, // cap on lender-added debt; None is 0
+    pub fee_repay_policy: Option<FeeRepayPolicy>, // None is RepayFirstSpend
+    pub proposal_threshold: Option<u32>, // None disables proposals
     pub fee_lend_repay_wallet: Addr,
     pub home_network: String,
     pub pair_contracts: Vec<PairContract>,
@@ -51,12 +84,16 @@ impl State {
         Ok(())
     }
 
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_by.is_some()
+    }
+
     pub fn is_active_hot_wallet(&self, addr: Addr) -> StdResult<bool> {
         let this_wallet_opt: Option<&HotWallet> =
             self.hot_wallets.iter().find(|a| a.address == addr);
         match this_wallet_opt {
             None => Ok(false),
-            Some(_) => Ok(true),
+            Some(wallet) => Ok(!wallet.is_paused()),
         }
     }
 
@@ -120,6 +157,35 @@ impl State {
         }
     }
 
+    pub fn get_fee_repay_policy(&self) -> FeeRepayPolicy {
+        match self.fee_repay_policy.clone() {
+            None => FeeRepayPolicy::RepayFirstSpend,
+            Some(policy) => policy,
+        }
+    }
+
+    /// returns true if there is outstanding debt and its repay deadline has passed
+    pub fn is_past_fee_repay_deadline(&self, current_time: Timestamp) -> bool {
+        match self.get_fee_repay_policy() {
+            FeeRepayPolicy::RepayByDeadline { deadline } => {
+                self.uusd_fee_debt > Uint128::from(0u128) && current_time >= deadline
+            }
+            _ => false,
+        }
+    }
+
+    /// returns the native asset of the home network
+    pub fn get_home_denom(&self) -> Result<String, StdError> {
+        match self.home_network.clone() {
+            val if val == MAINNET_ID => Ok(MAINNET_DENOM.to_string()),
+            val if val == TESTNET_ID => Ok(TESTNET_DENOM.to_string()),
+            val if val == *"local" => Ok("testtokens".to_string()),
+            _ => Err(StdError::GenericErr {
+                msg: "No native asset known for this network".to_string(),
+            }),
+        }
+    }
+
     pub fn add_hot_wallet(&mut self, new_hot_wallet: HotWallet) {
         self.hot_wallets.push(new_hot_wallet);
     }
@@ -129,12 +195,30 @@ impl State {
             .retain(|wallet| wallet.address != doomed_hot_wallet);
     }
 
+    /// pauses or unpauses a hot wallet. Pausing leaves the wallet's spend
+    /// limits as they are, so unpausing doesn't reset spending
+    pub fn set_hot_wallet_paused(
+        &mut self,
+        addr: String,
+        paused: bool,
+    ) -> Result<(), ContractError> {
+        let this_wallet = self.maybe_get_hot_wallet_mut(addr)?;
+        this_wallet.paused = Some(paused);
+        Ok(())
+    }
+
     /// returns true if the address is a registered admin
     pub fn is_admin(&self, addr: String) -> bool {
         let addr: &str = &addr;
         self.admin == addr
     }
 
+    /// returns true if the address is the fee lender
+    pub fn is_fee_lender(&self, addr: String) -> bool {
+        let addr: &str = &addr;
+        self.fee_lend_repay_wallet == addr
+    }
+
     /// returns true if the address is pending to become a registered admin
     pub fn is_pending(&self, addr: String) -> bool {
         let addr: &str = &addr;
@@ -173,18 +257,48 @@ impl State {
             return Ok(get_admin_sourced_coin());
         }
         let this_wallet = self.maybe_get_hot_wallet_mut(addr)?;
+        if this_wallet.is_paused() {
+            return Err(ContractError::HotWalletPaused {});
+        }
 
         // check if we should reset to full spend limit again
         // (i.e. reset time has passed)
         if current_time.seconds() > this_wallet.current_period_reset {
-            let new_dt = this_wallet.reset_period(current_time);
-            match new_dt {
-                Ok(()) => this_wallet.process_spend_vec(deps, spend),
-                Err(e) => Err(e),
-            }
-        } else {
-            this_wallet.process_spend_vec(deps, spend)
+            this_wallet.reset_period(current_time)?;
         }
+        this_wallet.process_spend_vec(deps, spend)
+    }
+
+    /// errors if a transaction by addr now would go over its hot wallet's
+    /// transaction limits. Admin has none
+    pub fn check_tx_limits(
+        &self,
+        current_time: Timestamp,
+        addr: String,
+    ) -> Result<(), ContractError> {
+        if self.is_admin(addr.clone()) {
+            return Ok(());
+        }
+        let this_wallet = self.maybe_get_hot_wallet(addr)?;
+        this_wallet.check_tx_limits(current_time, this_wallet.should_reset(current_time))
+    }
+
+    /// counts one transaction, however many messages it has, toward
+    /// addr's hot wallet transaction limits
+    pub fn record_tx(
+        &mut self,
+        current_time: Timestamp,
+        addr: String,
+    ) -> Result<(), ContractError> {
+        if self.is_admin(addr.clone()) {
+            return Ok(());
+        }
+        let this_wallet = self.maybe_get_hot_wallet_mut(addr)?;
+        if this_wallet.should_reset(current_time) {
+            this_wallet.reset_period(current_time)?;
+        }
+        this_wallet.record_tx(current_time);
+        Ok(())
     }
 
     pub fn check_spend_limits(
@@ -198,15 +312,65 @@ impl State {
             return Ok(get_admin_sourced_coin());
         }
         let this_wallet = self.maybe_get_hot_wallet(addr)?;
+        if this_wallet.is_paused() {
+            return Err(ContractError::HotWalletPaused {});
+        }
 
         // check if we should reset to full spend limit again
         // (i.e. reset time has passed)
-        if this_wallet.should_reset(current_time) {
-            this_wallet.check_spend_vec(deps, spend, true)
-        } else {
-            this_wallet.check_spend_vec(deps, spend, false)
-        }
+        let should_reset = this_wallet.should_reset(current_time);
+        this_wallet.check_tx_limits(current_time, should_reset)?;
+        this_wallet.check_spend_vec(deps, spend, should_reset)
     }
 }
 
+/// A single loan of fees to the proxy, kept so that users can audit
+/// every increase in debt.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct FeeDebtRecord {
+    pub lender: Addr,
+    pub amount: Uint128,
+    pub memo: String,
+    pub time: Timestamp,
+    pub block_height: u64,
+}
+
+/// A single hot wallet spend, kept for accounting exports.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SpendRecord {
+    pub time: Timestamp,
+    pub block_height: u64,
+    pub msg_summary: String,
+    pub coins: Vec<Coin>,
+    pub usd_amount: Uint128,
+    pub sources: Sources,
+}
+
+/// The signers behind the admin (e.g. multisig members). Kept with the admin
+/// they were set for, so a set left over from a previous admin is not reported.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SignerSet {
+    pub admin: Addr,
+    pub signers: Vec<Addr>,
+}
+
 pub const STATE: Item<State> = Item::new("state");
+pub const SIGNERS: Item<SignerSet> = Item::new("signers");
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const FEE_DEBT_HISTORY: Map<u64, FeeDebtRecord> = Map::new("fee_debt_history");
+pub const FEE_DEBT_HISTORY_COUNT: Item<u64> = Item::new("fee_debt_history_count");
+pub const SPEND_HISTORY: Map<(Addr, u64), SpendRecord> = Map::new("spend_history");
+pub const SPEND_HISTORY_COUNT: Item<u64> = Item::new("spend_history_count");
+pub const GUARDIANS: Item<GuardianSet> = Item::new("guardians");
+pub const RECOVERY: Item<Recovery> = Item::new("recovery");
+pub const HEIR: Item<Heir> = Item::new("heir");
+pub const SCHEDULED_TRANSFERS: Map<u64, ScheduledTransfer> = Map::new("scheduled_transfers");
+pub const SCHEDULED_TRANSFER_COUNT: Item<u64> = Item::new("scheduled_transfer_count");
+pub const SUBSCRIPTIONS: Map<u64, Subscription> = Map::new("subscriptions");
+pub const SUBSCRIPTION_COUNT: Item<u64> = Item::new("subscription_count");
+pub const SESSION_KEYS: Map<&Addr, SessionKey> = Map::new("session_keys");
+pub const SIGNING_KEYS: Map<&Addr, Binary> = Map::new("signing_keys");
+/// next nonce for each hot wallet's signed payloads. Kept when a hot
+/// wallet is removed, so its old payloads can't be replayed if re-added
+pub const SIGNED_NONCES: Map<&Addr, u64> = Map::new("signed_nonces");
@@ -40,7 +40,33 @@ pub enum WasmmsgType {
     Cw20UploadLogo,
 }
 
+impl SubmsgType {
+    /// returns true for the message types that spend funds, and so
+    /// are subject to spend limits
+    pub fn is_spend(&self) -> bool {
+        matches!(
+            self,
+            SubmsgType::BankSend
+                | SubmsgType::BankBurn
+                | SubmsgType::ExecuteWasm(WasmmsgType::Cw20Transfer)
+                | SubmsgType::ExecuteWasm(WasmmsgType::Cw20Send)
+                | SubmsgType::ExecuteWasm(WasmmsgType::Cw20Burn)
+                | SubmsgType::ExecuteWasm(WasmmsgType::Cw20IncreaseAllowance)
+        )
+    }
+}
+
 impl PendingSubmsg {
+    pub fn new(msg: CosmosMsg) -> Self {
+        PendingSubmsg {
+            msg,
+            contract_addr: None,
+            binarymsg: None,
+            funds: vec![],
+            ty: SubmsgType::Unknown,
+        }
+    }
+
     pub fn add_funds(&mut self, funds: Vec<Coin>) {
         for fund in funds {
             self.funds.push(fund);
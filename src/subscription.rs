@@ -0,0 +1,65 @@
+use cosmwasm_std::{Coin, StdError, StdResult, Timestamp};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::hot_wallet::{next_period_start, PeriodType};
+use crate::ContractError;
+
+/// A recurring payment registered by admin. Once `next_due` passes,
+/// anyone can trigger it, and `next_due` moves on a period as hot
+/// wallet resets do: `period_multiple` days, or to the first of the
+/// month `period_multiple` months on.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Subscription {
+    pub recipient: String,
+    pub amount: Vec<Coin>,
+    pub period: PeriodType,
+    pub period_multiple: u16,
+    pub next_due: Timestamp,
+    /// no payments fall due at or after this time
+    pub end: Option<Timestamp>,
+}
+
+impl Subscription {
+    pub fn assert_is_valid(&self) -> StdResult<()> {
+        if self.period_multiple == 0 {
+            return Err(StdError::GenericErr {
+                msg: "Subscription period multiple must be at least 1.".to_string(),
+            });
+        }
+        if self.amount.is_empty() || self.amount.iter().any(|coin| coin.amount.is_zero()) {
+            return Err(StdError::GenericErr {
+                msg: "Subscription amount cannot be empty or zero.".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn is_due(&self, current_time: Timestamp) -> bool {
+        current_time >= self.next_due && !self.is_finished()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        match self.end {
+            Some(end) => self.next_due >= end,
+            None => false,
+        }
+    }
+
+    /// moves next_due on by one period
+    pub fn advance(&mut self) -> Result<(), ContractError> {
+        let next_due =
+            next_period_start(&self.period, self.period_multiple, self.next_due.seconds())?;
+        // Timestamp keeps nanoseconds in a u64
+        self.next_due = next_due
+            .checked_mul(1_000_000_000)
+            .map(Timestamp::from_nanos)
+            .ok_or_else(|| ContractError::DayUpdateError("time out of range".to_string()))?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SubscriptionsResponse {
+    pub subscriptions: Vec<(u64, Subscription)>,
+}
@@ -0,0 +1,466 @@
+//! A cw-multi-test harness for the proxy, for this crate's integration
+//! tests and for downstream contracts through the `test-utils` feature.
+//!
+//! ```ignore
+//! let mut suite = SuiteBuilder::new()
+//!     .with_daily_hot_wallet("hotcarl", 1_000_000)
+//!     .with_funds(&coins(1_000_000, SUITE_DENOM))
+//!     .build();
+//! suite.send_native("hotcarl", "diane", &coins(1_000, SUITE_DENOM)).unwrap();
+//! suite.advance_days(1);
+//! ```
+//!
+//! Spends are priced by mock dex pairs (see `mock_pair`). Unless others
+//! are given, one JunoSwap-style pair prices `SUITE_DENOM` at 100 USDC
+//! micro units each.
+
+use anyhow::Result;
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Response,
+    StdResult, Timestamp, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use derivative::Derivative;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::MAINNET_AXLUSDC_IBC;
+use crate::hot_wallet::{CoinLimit, HotWallet, PeriodType, SpendLimitStatusResponse};
+use crate::mock_pair::{MockPair, MockPairExecuteMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::pair_contract::{PairContract, PairMessageType};
+use crate::state::STATE;
+
+pub const SUITE_OWNER: &str = "owner";
+pub const SUITE_ADMIN: &str = "alice";
+/// the home denom of the "local" network the proxy runs on
+pub const SUITE_DENOM: &str = "testtokens";
+
+/// The proxy's instantiate message, with the mock pairs that replace
+/// the local network's default pairs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct SuiteInstantiateMsg {
+    proxy: InstantiateMsg,
+    pair_contracts: Vec<PairContract>,
+}
+
+fn instantiate_with_pairs(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: SuiteInstantiateMsg,
+) -> StdResult<Response> {
+    let res = crate::contract::instantiate(deps.branch(), env, info, msg.proxy)?;
+    let mut cfg = STATE.load(deps.storage)?;
+    cfg.pair_contracts = msg.pair_contracts;
+    STATE.save(deps.storage, &cfg)?;
+    Ok(res)
+}
+
+fn contract_cw1() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        instantiate_with_pairs,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn contract_mock_pair() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::mock_pair::execute,
+        crate::mock_pair::instantiate,
+        crate::mock_pair::query,
+    );
+    Box::new(contract)
+}
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// A hot wallet with a USD limit that resets daily, from its first spend
+pub fn daily_hot_wallet(address: &str, usd_limit: u64) -> HotWallet {
+    HotWallet {
+        address: address.to_string(),
+        current_period_reset: 0,
+        period_type: PeriodType::DAYS,
+        period_multiple: 1,
+        spend_limits: vec![CoinLimit {
+            denom: MAINNET_AXLUSDC_IBC.to_string(),
+            amount: usd_limit,
+            limit_remaining: usd_limit,
+        }],
+        usdc_denom: Some("true".to_string()),
+        default: Some(true),
+        paused: None,
+        max_tx_per_period: None,
+        min_seconds_between_tx: None,
+        tx_count: None,
+        last_tx_time: None,
+    }
+}
+
+/// One JunoSwap-style pair pricing `SUITE_DENOM` at 100 USDC micro units
+pub fn default_pair() -> MockPair {
+    MockPair {
+        query_format: PairMessageType::JunoType,
+        denom1: SUITE_DENOM.to_string(),
+        denom2: MAINNET_AXLUSDC_IBC.to_string(),
+        reserve1: Uint128::from(1_000_000_000_000u128),
+        reserve2: Uint128::from(100_000_000_000_000u128),
+    }
+}
+
+pub struct SuiteBuilder {
+    admin: String,
+    hot_wallets: Vec<HotWallet>,
+    fee_debt: u128,
+    funds: Vec<Coin>,
+    cw20_funds: Vec<(String, u128)>,
+    pairs: Vec<(MockPair, PairMessageType)>,
+}
+
+impl Default for SuiteBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SuiteBuilder {
+    pub fn new() -> Self {
+        SuiteBuilder {
+            admin: SUITE_ADMIN.to_string(),
+            hot_wallets: vec![],
+            fee_debt: 0,
+            funds: vec![],
+            cw20_funds: vec![],
+            pairs: vec![],
+        }
+    }
+
+    pub fn with_admin(mut self, admin: &str) -> Self {
+        self.admin = admin.to_string();
+        self
+    }
+
+    pub fn with_hot_wallet(mut self, hot_wallet: HotWallet) -> Self {
+        self.hot_wallets.push(hot_wallet);
+        self
+    }
+
+    pub fn with_daily_hot_wallet(self, address: &str, usd_limit: u64) -> Self {
+        self.with_hot_wallet(daily_hot_wallet(address, usd_limit))
+    }
+
+    /// USD fee debt, in USDC micro units, owed from instantiation
+    pub fn with_fee_debt(mut self, fee_debt: u128) -> Self {
+        self.fee_debt = fee_debt;
+        self
+    }
+
+    /// Native funds held by the proxy
+    pub fn with_funds(mut self, funds: &[Coin]) -> Self {
+        self.funds.extend_from_slice(funds);
+        self
+    }
+
+    /// Creates a cw20 token, with `amount` held by the proxy. `symbol`
+    /// must be 3 to 12 letters
+    pub fn with_cw20_funds(mut self, symbol: &str, amount: u128) -> Self {
+        self.cw20_funds.push((symbol.to_string(), amount));
+        self
+    }
+
+    /// Adds a mock pair for the proxy to price spends with, in place of
+    /// the default pair
+    pub fn with_pair(self, pair: MockPair) -> Self {
+        let query_format = pair.query_format.clone();
+        self.with_pair_queried_as(pair, query_format)
+    }
+
+    /// Registers `pair` with the proxy as answering `query_format`
+    /// queries, such as to test a misconfigured pair
+    pub fn with_pair_queried_as(mut self, pair: MockPair, query_format: PairMessageType) -> Self {
+        self.pairs.push((pair, query_format));
+        self
+    }
+
+    pub fn build(self) -> Suite {
+        let owner = Addr::unchecked(SUITE_OWNER);
+        let funds = self.funds.clone();
+        let mut app = App::new(|router, _, storage| {
+            router.bank.init_balance(storage, &owner, funds).unwrap();
+        });
+        let cw1_id = app.store_code(contract_cw1());
+        let pair_id = app.store_code(contract_mock_pair());
+        let cw20_id = app.store_code(contract_cw20());
+
+        let pairs = match self.pairs.is_empty() {
+            true => vec![(default_pair(), PairMessageType::JunoType)],
+            false => self.pairs,
+        };
+        let pairs: Vec<PairContract> = pairs
+            .into_iter()
+            .map(|(pair, query_format)| {
+                let contract_addr = app
+                    .instantiate_contract(pair_id, owner.clone(), &pair, &[], "Mock pair", None)
+                    .unwrap();
+                PairContract {
+                    contract_addr: contract_addr.to_string(),
+                    denom1: pair.denom1,
+                    denom2: pair.denom2,
+                    query_format,
+                }
+            })
+            .collect();
+
+        let proxy = app
+            .instantiate_contract(
+                cw1_id,
+                owner.clone(),
+                &SuiteInstantiateMsg {
+                    proxy: InstantiateMsg {
+                        admin: self.admin.clone(),
+                        hot_wallets: self.hot_wallets,
+                        uusd_fee_debt: Uint128::from(self.fee_debt),
+                        fee_lend_repay_wallet: "test_repay_address".to_string(),
+                        home_network: "local".to_string(),
+                        signers: [
+                            "testsigner1".to_string(),
+                            "testsigner2".to_string(),
+                            "testsigner3".to_string(),
+                        ]
+                        .to_vec(),
+                    },
+                    pair_contracts: pairs.clone(),
+                },
+                &[],
+                "Obi proxy",
+                None,
+            )
+            .unwrap();
+        if !self.funds.is_empty() {
+            app.send_tokens(owner.clone(), proxy.clone(), &self.funds)
+                .unwrap();
+        }
+
+        let cw20s = self
+            .cw20_funds
+            .into_iter()
+            .map(|(symbol, amount)| {
+                let token = app
+                    .instantiate_contract(
+                        cw20_id,
+                        owner.clone(),
+                        &cw20_base::msg::InstantiateMsg {
+                            name: symbol.clone(),
+                            symbol: symbol.clone(),
+                            decimals: 6,
+                            initial_balances: vec![Cw20Coin {
+                                address: proxy.to_string(),
+                                amount: Uint128::from(amount),
+                            }],
+                            mint: None,
+                            marketing: None,
+                        },
+                        &[],
+                        symbol.clone(),
+                        None,
+                    )
+                    .unwrap();
+                (symbol, token)
+            })
+            .collect();
+
+        Suite {
+            app,
+            owner,
+            admin: Addr::unchecked(self.admin),
+            proxy,
+            pairs,
+            cw20s,
+        }
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct Suite {
+    /// Application mock
+    #[derivative(Debug = "ignore")]
+    app: App,
+    /// Special account that instantiates everything
+    pub owner: Addr,
+    pub admin: Addr,
+    pub proxy: Addr,
+    /// the pairs the proxy prices spends with
+    pub pairs: Vec<PairContract>,
+    /// cw20 tokens by symbol
+    cw20s: Vec<(String, Addr)>,
+}
+
+impl Suite {
+    /// For storing and instantiating other contracts against the proxy
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    pub fn cw20(&self, symbol: &str) -> Addr {
+        self.cw20s
+            .iter()
+            .find(|(token_symbol, _)| token_symbol == symbol)
+            .map(|(_, token)| token.clone())
+            .unwrap_or_else(|| panic!("no cw20 token {}", symbol))
+    }
+
+    pub fn block_time(&self) -> Timestamp {
+        self.app.block_info().time
+    }
+
+    /// Moves block time (and height, at 5 second blocks) forward
+    pub fn advance_seconds(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += seconds / 5;
+        });
+    }
+
+    pub fn advance_days(&mut self, days: u64) {
+        self.advance_seconds(days * 24 * 60 * 60);
+    }
+
+    /// Sets the reserves of `self.pairs[pair]`
+    pub fn set_reserves(
+        &mut self,
+        pair: usize,
+        reserve1: u128,
+        reserve2: u128,
+    ) -> Result<AppResponse> {
+        let pair = Addr::unchecked(self.pairs[pair].contract_addr.clone());
+        self.app.execute_contract(
+            self.owner.clone(),
+            pair,
+            &MockPairExecuteMsg::SetReserves {
+                reserve1: Uint128::from(reserve1),
+                reserve2: Uint128::from(reserve2),
+            },
+            &[],
+        )
+    }
+
+    /// Sends any message to the proxy
+    pub fn execute(&mut self, sender: &str, msg: &ExecuteMsg) -> Result<AppResponse> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.proxy.clone(), msg, &[])
+    }
+
+    /// Has `sender` ask the proxy to execute `msgs`
+    pub fn execute_msgs(&mut self, sender: &str, msgs: Vec<CosmosMsg>) -> Result<AppResponse> {
+        self.execute(sender, &ExecuteMsg::Execute { msgs })
+    }
+
+    pub fn send_native(
+        &mut self,
+        sender: &str,
+        recipient: &str,
+        amount: &[Coin],
+    ) -> Result<AppResponse> {
+        self.execute_msgs(
+            sender,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: amount.to_vec(),
+            })],
+        )
+    }
+
+    pub fn transfer_cw20(
+        &mut self,
+        sender: &str,
+        symbol: &str,
+        recipient: &str,
+        amount: u128,
+    ) -> Result<AppResponse> {
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.cw20(symbol).to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::from(amount),
+            })?,
+            funds: vec![],
+        });
+        self.execute_msgs(sender, vec![msg])
+    }
+
+    /// Adds a hot wallet, as admin
+    pub fn add_hot_wallet(&mut self, hot_wallet: HotWallet) -> Result<AppResponse> {
+        let admin = self.admin.to_string();
+        self.execute(
+            &admin,
+            &ExecuteMsg::AddHotWallet {
+                new_hot_wallet: hot_wallet,
+            },
+        )
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: &QueryMsg) -> StdResult<T> {
+        self.app.wrap().query_wasm_smart(self.proxy.clone(), msg)
+    }
+
+    pub fn spend_limit_status(&self, wallet: &str) -> SpendLimitStatusResponse {
+        self.query(&QueryMsg::SpendLimitStatus {
+            wallet: wallet.to_string(),
+        })
+        .unwrap()
+    }
+
+    pub fn native_balance(&self, address: &str, denom: &str) -> u128 {
+        self.app
+            .wrap()
+            .query_balance(address, denom)
+            .unwrap()
+            .amount
+            .u128()
+    }
+
+    pub fn cw20_balance(&self, symbol: &str, address: &str) -> u128 {
+        let res: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                self.cw20(symbol),
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        res.balance.u128()
+    }
+
+    pub fn assert_native_balance(&self, address: &str, denom: &str, expected: u128) {
+        assert_eq!(
+            self.native_balance(address, denom),
+            expected,
+            "{} balance of {}",
+            denom,
+            address
+        );
+    }
+
+    pub fn assert_cw20_balance(&self, symbol: &str, address: &str, expected: u128) {
+        assert_eq!(
+            self.cw20_balance(symbol, address),
+            expected,
+            "{} balance of {}",
+            symbol,
+            address
+        );
+    }
+}
@@ -1,7 +1,82 @@
-use cosmwasm_std::{Coin, Uint128};
+use cosmwasm_std::{
+    from_binary, to_binary, Coin, ContractResult, QuerierResult, StdResult, SystemError,
+    SystemResult, Uint128, WasmQuery,
+};
 
+use crate::pair_contract::PairMessageType;
+use crate::pair_contract_defaults::get_local_pair_contracts;
+use crate::simulation::{
+    DexQueryMsgFormatted, ReverseSimulationResponse, SimulationResponse,
+    Token1ForToken2PriceResponse, Token2ForToken1PriceResponse,
+};
 use crate::{sourced_coin::SourcedCoin, sources::Source, sources::Sources, ContractError};
 
+/// Answers price queries to the local pair contracts with the fixed
+/// conversions of `get_test_sourced_coin`, for use with
+/// `MockQuerier::update_wasm`.
+pub fn mock_local_pair_query(query: &WasmQuery) -> QuerierResult {
+    let (contract_addr, msg) = match query {
+        WasmQuery::Smart { contract_addr, msg } => (contract_addr, msg),
+        _ => {
+            return SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "only smart queries are mocked".to_string(),
+            })
+        }
+    };
+    let pair = match get_local_pair_contracts()
+        .to_vec()
+        .into_iter()
+        .find(|pair| pair.contract_addr == *contract_addr)
+    {
+        Some(pair) => pair,
+        None => {
+            return SystemResult::Err(SystemError::NoSuchContract {
+                addr: contract_addr.clone(),
+            })
+        }
+    };
+    let query_msg: DexQueryMsgFormatted = match from_binary(msg) {
+        Ok(query_msg) => query_msg,
+        Err(e) => return SystemResult::Ok(ContractResult::Err(e.to_string())),
+    };
+    let (flip_assets, amount) = match &query_msg {
+        DexQueryMsgFormatted::Simulation(msg) => (false, msg.offer_asset.amount),
+        DexQueryMsgFormatted::ReverseSimulation(msg) => (true, msg.ask_asset.amount),
+        DexQueryMsgFormatted::Token1ForToken2Price(msg) => (false, msg.token1_amount),
+        DexQueryMsgFormatted::Token2ForToken1Price(msg) => (true, msg.token2_amount),
+    };
+    // mirrors PairContract::create_query_msg
+    let (from_denom, to_denom) = match (pair.query_format, flip_assets) {
+        (PairMessageType::LoopType, false) => (pair.denom1, pair.denom2.clone()),
+        (PairMessageType::LoopType, true) => (pair.denom2.clone(), pair.denom2),
+        (PairMessageType::JunoType, false) => (pair.denom1, pair.denom2),
+        (PairMessageType::JunoType, true) => (pair.denom2, pair.denom1),
+    };
+    let converted = match get_test_sourced_coin((from_denom, to_denom), amount, flip_assets) {
+        Ok(converted) => converted.coin.amount,
+        Err(e) => return SystemResult::Ok(ContractResult::Err(e.to_string())),
+    };
+    let response: StdResult<_> = match query_msg {
+        DexQueryMsgFormatted::Simulation(_) => to_binary(&SimulationResponse {
+            commission_amount: Uint128::zero(),
+            return_amount: converted,
+            spread_amount: Uint128::zero(),
+        }),
+        DexQueryMsgFormatted::ReverseSimulation(_) => to_binary(&ReverseSimulationResponse {
+            commission_amount: Uint128::zero(),
+            offer_amount: converted,
+            spread_amount: Uint128::zero(),
+        }),
+        DexQueryMsgFormatted::Token1ForToken2Price(_) => to_binary(&Token1ForToken2PriceResponse {
+            token2_amount: converted,
+        }),
+        DexQueryMsgFormatted::Token2ForToken1Price(_) => to_binary(&Token2ForToken1PriceResponse {
+            token1_amount: converted,
+        }),
+    };
+    SystemResult::Ok(response.into())
+}
+
 pub fn get_test_sourced_coin(
     denoms: (String, String),
     amount: Uint128,
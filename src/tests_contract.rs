@@ -4,9 +4,13 @@ pub const HOT_WALLET: &str = "hotcarl";
 #[cfg(test)]
 mod tests {
     use crate::contract::{
-        execute, query_admin, query_can_execute, query_can_spend, query_hot_wallets,
+        execute, query_admin, query_can_execute, query_can_spend, query_fee_debt,
+        query_fee_debt_history, query_hot_wallets, query_pending_admin, query_signers,
+        query_simulate_execute, query_spend_history, query_spend_limit_status,
     };
     use crate::hot_wallet::PeriodType;
+    use crate::state::{FeeRepayPolicy, STATE};
+    use crate::submsgs::SubmsgType;
     /* use crate::defaults::get_local_pair_contracts; */
     use super::*;
     use crate::msg::{AdminResponse, Cw20ExecuteMsg, ExecuteMsg};
@@ -70,9 +74,10 @@ mod tests {
         };
         let info = mock_info(NEW_ADMIN, &[]);
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-        assert_eq!(res.events.len(), 1);
+        assert_eq!(res.events.len(), 2);
+        assert_eq!(res.events[0].ty, "obi_admin_change");
         assert_eq!(
-            res.events[0].attributes[0],
+            res.events[1].attributes[0],
             Attribute::new("signer".to_string(), "test_confirm_admin".to_string())
         );
 
@@ -83,6 +88,795 @@ mod tests {
         assert_eq!(query_admin(deps.as_ref()).unwrap(), expected);
     }
 
+    #[test]
+    fn signers_are_stored() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env,
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        let res = query_signers(deps.as_ref()).unwrap();
+        assert_eq!(res.admin, ADMIN.to_string());
+        assert_eq!(
+            res.signers,
+            vec![
+                "testsigner1".to_string(),
+                "testsigner2".to_string(),
+                "testsigner3".to_string()
+            ]
+        );
+
+        // only admin can update them, and without duplicates
+        let update_msg = ExecuteMsg::UpdateSigners {
+            signers: vec!["testsigner4".to_string(), "testsigner4".to_string()],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ANYONE, &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update_msg).unwrap_err();
+        let update_msg = ExecuteMsg::UpdateSigners {
+            signers: vec!["testsigner4".to_string()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update_msg).unwrap();
+        assert_eq!(
+            res.events[0].attributes,
+            vec![Attribute::new("signer", "testsigner4")]
+        );
+        let res = query_signers(deps.as_ref()).unwrap();
+        assert_eq!(res.signers, vec!["testsigner4".to_string()]);
+
+        // a new admin's signers replace the old ones
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::ProposeUpdateAdmin {
+                new_admin: NEW_ADMIN.to_string(),
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(NEW_ADMIN, &[]),
+            ExecuteMsg::ConfirmUpdateAdmin {
+                signers: vec!["testsigner5".to_string()],
+            },
+        )
+        .unwrap();
+        let res = query_signers(deps.as_ref()).unwrap();
+        assert_eq!(res.admin, NEW_ADMIN.to_string());
+        assert_eq!(res.signers, vec!["testsigner5".to_string()]);
+    }
+
+    #[test]
+    fn spend_history_is_recorded_and_pruned() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        for amount in [2_000u128, 3_000u128] {
+            test_spend_bank(
+                deps.as_mut(),
+                current_env.clone(),
+                RECEIVER.to_string(),
+                coins(amount, "testtokens"),
+                mock_info(HOT_WALLET, &[]),
+            )
+            .unwrap();
+        }
+        let res = query_spend_history(deps.as_ref(), HOT_WALLET.to_string(), None, None).unwrap();
+        assert_eq!(res.records.len(), 2);
+        let (id, record) = res.records[1].clone();
+        assert_eq!(id, 1);
+        assert_eq!(record.coins, coins(3_000u128, "testtokens"));
+        assert_eq!(record.usd_amount, Uint128::from(300_000u128));
+        assert_eq!(record.msg_summary, format!("bank send to {}", RECEIVER));
+        assert_eq!(record.time, current_env.block.time);
+        let res =
+            query_spend_history(deps.as_ref(), HOT_WALLET.to_string(), Some(0), None).unwrap();
+        assert_eq!(res.records.len(), 1);
+        let res = query_spend_history(deps.as_ref(), ADMIN.to_string(), None, None).unwrap();
+        assert_eq!(res.records, vec![]);
+
+        // records older than 90 days are pruned on the next spend
+        let mut later_env = current_env;
+        later_env.block.time = later_env.block.time.plus_seconds(91 * 24 * 60 * 60);
+        test_spend_bank(
+            deps.as_mut(),
+            later_env.clone(),
+            RECEIVER.to_string(),
+            coins(1_000u128, "testtokens"),
+            mock_info(HOT_WALLET, &[]),
+        )
+        .unwrap();
+        let res = query_spend_history(deps.as_ref(), HOT_WALLET.to_string(), None, None).unwrap();
+        assert_eq!(res.records.len(), 1);
+        assert_eq!(res.records[0].0, 2);
+        assert_eq!(res.records[0].1.time, later_env.block.time);
+    }
+
+    #[test]
+    fn spend_limit_status_applies_due_reset() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        test_spend_bank(
+            deps.as_mut(),
+            current_env.clone(),
+            RECEIVER.to_string(),
+            coins(5_000u128, "testtokens"),
+            mock_info(HOT_WALLET, &[]),
+        )
+        .unwrap();
+        let res =
+            query_spend_limit_status(deps.as_ref(), current_env.clone(), HOT_WALLET.to_string())
+                .unwrap();
+        assert_eq!(res.limit, 1_000_000u64);
+        assert_eq!(res.limit_remaining, 500_000u64);
+        assert_eq!(res.period_end.seconds(), current_env.block.time.seconds());
+        assert!(!res.paused);
+        let testtokens = res
+            .native_equivalents
+            .iter()
+            .find(|sourced| sourced.coin.denom == "testtokens")
+            .unwrap();
+        // same test pricing as fee repayment, where 1_000_000 is 100 testtokens
+        assert_eq!(testtokens.coin.amount, Uint128::from(50u128));
+
+        // once the period has elapsed, the reset shows before the next spend
+        let mut later_env = current_env;
+        later_env.block.time = later_env.block.time.plus_seconds(10);
+        let res =
+            query_spend_limit_status(deps.as_ref(), later_env.clone(), HOT_WALLET.to_string())
+                .unwrap();
+        assert_eq!(res.limit_remaining, 1_000_000u64);
+        assert_eq!(
+            res.period_end.seconds(),
+            later_env.block.time.seconds() + 86_400
+        );
+        // and nothing is saved
+        let res = query_hot_wallets(deps.as_ref()).unwrap();
+        assert_eq!(
+            res.hot_wallets[0].spend_limits[0].limit_remaining,
+            500_000u64
+        );
+
+        query_spend_limit_status(deps.as_ref(), later_env.clone(), ANYONE.to_string()).unwrap_err();
+
+        // a period ending past what Timestamp can hold is an error, not an overflow
+        let mut cfg = STATE.load(&deps.storage).unwrap();
+        cfg.hot_wallets[0].period_type = PeriodType::MONTHS;
+        cfg.hot_wallets[0].period_multiple = 7_000;
+        STATE.save(&mut deps.storage, &cfg).unwrap();
+        let err =
+            query_spend_limit_status(deps.as_ref(), later_env.clone(), HOT_WALLET.to_string())
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Generic error: {}",
+                ContractError::DayUpdateError("time out of range".to_string())
+            )
+        );
+
+        // a wallet without a limit is an error, not a panic
+        let mut cfg = STATE.load(&deps.storage).unwrap();
+        cfg.hot_wallets[0].spend_limits = vec![];
+        STATE.save(&mut deps.storage, &cfg).unwrap();
+        let err =
+            query_spend_limit_status(deps.as_ref(), later_env, HOT_WALLET.to_string()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Generic error: {}", ContractError::NoSpendLimit {})
+        );
+    }
+
+    #[test]
+    fn simulate_execute_gives_verdict_per_msg() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(1_000_000u128),
+                denom: "testtokens".to_string(),
+            },
+        );
+        let send = |amount: u128| {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: RECEIVER.to_string(),
+                amount: coins(amount, "testtokens"),
+            })
+        };
+        let res = query_simulate_execute(
+            deps.as_ref(),
+            current_env.clone(),
+            HOT_WALLET.to_string(),
+            vec![
+                send(5_000),
+                send(6_000),
+                send(4_000),
+                CosmosMsg::Staking(StakingMsg::Delegate {
+                    validator: "validator".to_string(),
+                    amount: coin(1_000u128, "testtokens"),
+                }),
+            ],
+        )
+        .unwrap();
+        assert!(!res.can_execute);
+        assert_eq!(res.error, None);
+        assert_eq!(res.msgs.len(), 4);
+
+        // the first spend repays the fee debt and uses half the limit
+        let first = res.msgs[0].clone();
+        assert_eq!(first.msg_type, SubmsgType::BankSend);
+        assert_eq!(first.fee_repay.unwrap().coin, coin(100, "testtokens"));
+        assert_eq!(
+            first.usd_value.unwrap().coin.amount,
+            Uint128::from(500_000u128)
+        );
+        assert_eq!(first.limit_remaining, Some(500_000u64));
+        assert_eq!(first.error, None);
+
+        // the second goes over what's left, and the rest are checked as
+        // if it had been left out
+        assert!(res.msgs[1].error.is_some());
+        assert_eq!(res.msgs[1].fee_repay, None);
+        let third = res.msgs[2].clone();
+        assert_eq!(third.fee_repay, None);
+        assert_eq!(
+            third.usd_value.unwrap().coin.amount,
+            Uint128::from(400_000u128)
+        );
+        assert_eq!(third.limit_remaining, Some(100_000u64));
+        assert_eq!(third.error, None);
+        assert_eq!(res.msgs[3].msg_type, SubmsgType::Unknown);
+        assert_eq!(
+            res.msgs[3].error,
+            Some(ContractError::BadMessageType("unknown".to_string()).to_string())
+        );
+
+        // nothing was changed
+        assert_eq!(
+            query_fee_debt(deps.as_ref()).unwrap().uusd_fee_debt,
+            Uint128::from(1_000_000u128)
+        );
+        let res = query_hot_wallets(deps.as_ref()).unwrap();
+        assert_eq!(
+            res.hot_wallets[0].spend_limits[0].limit_remaining,
+            1_000_000u64
+        );
+
+        let res = query_simulate_execute(
+            deps.as_ref(),
+            current_env,
+            HOT_WALLET.to_string(),
+            vec![send(5_000)],
+        )
+        .unwrap();
+        assert!(res.can_execute);
+    }
+
+    #[test]
+    fn sim_execute_changes_nothing() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(1_000_000u128),
+                denom: "testtokens".to_string(),
+            },
+        );
+        let res = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::SimExecute {
+                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: RECEIVER.to_string(),
+                    amount: coins(5_000u128, "testtokens"),
+                })],
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages, vec![]);
+
+        // fee debt is still owed, and the limit and history are untouched
+        assert_eq!(
+            query_fee_debt(deps.as_ref()).unwrap().uusd_fee_debt,
+            Uint128::from(1_000_000u128)
+        );
+        let res = query_hot_wallets(deps.as_ref()).unwrap();
+        assert_eq!(
+            res.hot_wallets[0].spend_limits[0].limit_remaining,
+            1_000_000u64
+        );
+        let res = query_spend_history(deps.as_ref(), HOT_WALLET.to_string(), None, None).unwrap();
+        assert_eq!(res.records, vec![]);
+
+        // but a simulation that would fail still errors
+        execute(
+            deps.as_mut(),
+            current_env,
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::SimExecute {
+                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: RECEIVER.to_string(),
+                    amount: coins(999_999_999_000u128, "testtokens"),
+                })],
+            },
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn batch_counts_as_one_transaction() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        let mut hot_wallet = query_hot_wallets(deps.as_ref()).unwrap().hot_wallets[0].clone();
+        hot_wallet.address = HOT_USDC_WALLET.to_string();
+        hot_wallet.max_tx_per_period = Some(1);
+        hot_wallet.min_seconds_between_tx = Some(60);
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::AddHotWallet {
+                new_hot_wallet: hot_wallet,
+            },
+        )
+        .unwrap();
+        let send = CosmosMsg::Bank(BankMsg::Send {
+            to_address: RECEIVER.to_string(),
+            amount: coins(1_000u128, "testtokens"),
+        });
+        let execute_msg = ExecuteMsg::Execute {
+            msgs: vec![send.clone(), send],
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(HOT_USDC_WALLET, &[]),
+            execute_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 2);
+        let res = query_hot_wallets(deps.as_ref()).unwrap();
+        assert_eq!(res.hot_wallets[1].tx_count, Some(1));
+
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(HOT_USDC_WALLET, &[]),
+            execute_msg,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TooManyTransactions(1));
+    }
+
+    #[test]
+    fn authorized_contract_counts_toward_tx_limits() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        let mut hot_wallet = query_hot_wallets(deps.as_ref()).unwrap().hot_wallets[0].clone();
+        hot_wallet.address = HOT_USDC_WALLET.to_string();
+        hot_wallet.max_tx_per_period = Some(1);
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::AddHotWallet {
+                new_hot_wallet: hot_wallet,
+            },
+        )
+        .unwrap();
+        // DRINK, one of the authorized contracts
+        let execute_msg = ExecuteMsg::Execute {
+            msgs: vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "juno18c5uecrztn4rqakm23fskusasud7s8afujnl8yu54ule2kak5q4sdnvcz4"
+                    .to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: RECEIVER.to_string(),
+                    amount: Uint128::from(1_000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })],
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(HOT_USDC_WALLET, &[]),
+            execute_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let res = query_hot_wallets(deps.as_ref()).unwrap();
+        assert_eq!(res.hot_wallets[1].tx_count, Some(1));
+
+        let err = execute(
+            deps.as_mut(),
+            current_env,
+            mock_info(HOT_USDC_WALLET, &[]),
+            execute_msg,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TooManyTransactions(1));
+    }
+
+    #[test]
+    fn pause_and_revoke_hot_wallet() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        test_spend_bank(
+            deps.as_mut(),
+            current_env.clone(),
+            RECEIVER.to_string(),
+            coins(5_000u128, "testtokens"),
+            mock_info(HOT_WALLET, &[]),
+        )
+        .unwrap();
+
+        // only admin pauses other wallets
+        let pause_msg = ExecuteMsg::PauseHotWallet {
+            address: HOT_WALLET.to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ANYONE, &[]),
+            pause_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            pause_msg,
+        )
+        .unwrap();
+        let err = test_spend_bank(
+            deps.as_mut(),
+            current_env.clone(),
+            RECEIVER.to_string(),
+            coins(1_000u128, "testtokens"),
+            mock_info(HOT_WALLET, &[]),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::HotWalletPaused {});
+
+        // unpausing keeps the remaining limit
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::UnpauseHotWallet {
+                address: HOT_WALLET.to_string(),
+            },
+        )
+        .unwrap();
+        let res = query_hot_wallets(deps.as_ref()).unwrap();
+        assert_eq!(
+            res.hot_wallets[0].spend_limits[0].limit_remaining,
+            500_000u64
+        );
+
+        // a hot wallet can revoke itself, but only admin brings it back
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ANYONE, &[]),
+            ExecuteMsg::RevokeSelf {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::HotWalletDoesNotExist {});
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::RevokeSelf {},
+        )
+        .unwrap();
+        let res = query_can_spend(
+            deps.as_ref(),
+            current_env.clone(),
+            HOT_WALLET.to_string(),
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: RECEIVER.to_string(),
+                amount: coins(1_000u128, "testtokens"),
+            })],
+        )
+        .unwrap();
+        assert!(!res.can_spend);
+        let err = execute(
+            deps.as_mut(),
+            current_env,
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::UnpauseHotWallet {
+                address: HOT_WALLET.to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn freeze_blocks_hot_wallets() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SetGuardians {
+                guardians: vec!["guardian_one".to_string()],
+                threshold: 1,
+                recovery_delay: 86_400,
+            },
+        )
+        .unwrap();
+
+        // random addresses can't freeze, but guardians and hot wallets can
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ANYONE, &[]),
+            ExecuteMsg::Freeze {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::Freeze {},
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info("guardian_one", &[]),
+            ExecuteMsg::Freeze {},
+        )
+        .unwrap();
+
+        let spend_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: RECEIVER.to_string(),
+            amount: coins(9_000u128, "testtokens"),
+        });
+        let res = query_can_spend(
+            deps.as_ref(),
+            current_env.clone(),
+            HOT_WALLET.to_string(),
+            vec![spend_msg.clone()],
+        )
+        .unwrap();
+        assert!(!res.can_spend);
+        assert_eq!(
+            res.reason,
+            Some(format!("contract frozen by {}", HOT_WALLET))
+        );
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::Execute {
+                msgs: vec![spend_msg.clone()],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Frozen {});
+
+        // admin can still execute, and only admin can unfreeze
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::Execute {
+                msgs: vec![spend_msg.clone()],
+            },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info("guardian_one", &[]),
+            ExecuteMsg::Unfreeze {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::Unfreeze {},
+        )
+        .unwrap();
+        let res = query_can_spend(
+            deps.as_ref(),
+            current_env,
+            HOT_WALLET.to_string(),
+            vec![spend_msg],
+        )
+        .unwrap();
+        assert!(res.can_spend);
+        assert_eq!(res.reason, None);
+    }
+
+    #[test]
+    fn timelocked_admin_transfer() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        let delay_msg = ExecuteMsg::SetAdminTransferDelay {
+            delay_seconds: 86_400,
+        };
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ANYONE, &[]),
+            delay_msg.clone(),
+        )
+        .unwrap_err();
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            delay_msg,
+        )
+        .unwrap();
+
+        // nothing is pending yet
+        let res = query_pending_admin(deps.as_ref()).unwrap();
+        assert_eq!(res.pending, None);
+
+        let propose_msg = ExecuteMsg::ProposeUpdateAdmin {
+            new_admin: NEW_ADMIN.to_string(),
+        };
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            propose_msg.clone(),
+        )
+        .unwrap();
+        let executable_at = current_env.block.time.plus_seconds(86_400);
+        let res = query_pending_admin(deps.as_ref()).unwrap();
+        assert_eq!(res.pending, Some(NEW_ADMIN.to_string()));
+        assert_eq!(res.proposed_at, Some(current_env.block.time));
+        assert_eq!(res.executable_at, Some(executable_at));
+
+        // bob cannot confirm during the window...
+        let confirm_msg = ExecuteMsg::ConfirmUpdateAdmin { signers: vec![] };
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(NEW_ADMIN, &[]),
+            confirm_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AdminTransferTimelocked(executable_at.seconds())
+        );
+
+        // ...and alice can cancel during it
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ANYONE, &[]),
+            ExecuteMsg::CancelUpdateAdmin {},
+        )
+        .unwrap_err();
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::CancelUpdateAdmin {},
+        )
+        .unwrap();
+        let res = query_pending_admin(deps.as_ref()).unwrap();
+        assert_eq!(res.pending, None);
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(NEW_ADMIN, &[]),
+            confirm_msg.clone(),
+        )
+        .unwrap_err();
+
+        // if proposed again, bob can confirm once the window has passed
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            propose_msg,
+        )
+        .unwrap();
+        let mut later_env = current_env;
+        later_env.block.time = executable_at;
+        execute(
+            deps.as_mut(),
+            later_env,
+            mock_info(NEW_ADMIN, &[]),
+            confirm_msg,
+        )
+        .unwrap();
+        let expected = AdminResponse {
+            admin: NEW_ADMIN.to_string(),
+        };
+        assert_eq!(query_admin(deps.as_ref()).unwrap(), expected);
+    }
+
     #[test]
     fn execute_messages_has_proper_permissions() {
         let mut deps = mock_dependencies();
@@ -154,19 +948,70 @@ mod tests {
         });
 
         // owner can send
-        let res = query_can_execute(deps.as_ref(), ADMIN.to_string(), send_msg.clone()).unwrap();
+        let res = query_can_execute(
+            deps.as_ref(),
+            mock_env(),
+            ADMIN.to_string(),
+            send_msg.clone(),
+        )
+        .unwrap();
         assert!(res.can_execute);
 
         // owner can stake
-        let res = query_can_execute(deps.as_ref(), ADMIN.to_string(), staking_msg.clone()).unwrap();
+        let res = query_can_execute(
+            deps.as_ref(),
+            mock_env(),
+            ADMIN.to_string(),
+            staking_msg.clone(),
+        )
+        .unwrap();
         assert!(res.can_execute);
 
         // anyone cannot send
-        let res = query_can_execute(deps.as_ref(), ANYONE.to_string(), send_msg).unwrap();
+        let res =
+            query_can_execute(deps.as_ref(), mock_env(), ANYONE.to_string(), send_msg).unwrap();
         assert!(!res.can_execute);
 
         // anyone cannot stake
-        let res = query_can_execute(deps.as_ref(), ANYONE.to_string(), staking_msg).unwrap();
+        let res = query_can_execute(
+            deps.as_ref(),
+            mock_env(),
+            ANYONE.to_string(),
+            staking_msg.clone(),
+        )
+        .unwrap();
+        assert!(!res.can_execute);
+
+        // hot wallet can send within its limit, but not over it or stake
+        let hot_send = |amount: u128| {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: RECEIVER.to_string(),
+                amount: coins(amount, "testtokens"),
+            })
+        };
+        let res = query_can_execute(
+            deps.as_ref(),
+            mock_env(),
+            HOT_WALLET.to_string(),
+            hot_send(9_000),
+        )
+        .unwrap();
+        assert!(res.can_execute);
+        let res = query_can_execute(
+            deps.as_ref(),
+            mock_env(),
+            HOT_WALLET.to_string(),
+            hot_send(999_999_999_000),
+        )
+        .unwrap();
+        assert!(!res.can_execute);
+        let res = query_can_execute(
+            deps.as_ref(),
+            mock_env(),
+            HOT_WALLET.to_string(),
+            staking_msg,
+        )
+        .unwrap();
         assert!(!res.can_execute);
     }
 
@@ -385,6 +1230,316 @@ mod tests {
         );
     }
 
+    #[test]
+    fn repay_fee_debt_explicitly() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env,
+            Coin {
+                amount: Uint128::from(1_000_000u128),
+                denom: "ibc/EAC38D55372F38F1AFD68DF7FE9EF762DCF69F26520643CF3F9D292A738D8034"
+                    .to_string(),
+            },
+        );
+
+        // debt is $1, which the query quotes as 100 testtokens
+        let res = query_fee_debt(deps.as_ref()).unwrap();
+        assert_eq!(res.uusd_fee_debt, Uint128::from(1_000_000u128));
+        assert_eq!(res.fee_lend_repay_wallet, "test_repay_address".to_string());
+        assert_eq!(res.repay_quote.unwrap().coin, coin(100, "testtokens"));
+
+        // anyone cannot repay on behalf of the proxy
+        let execute_msg = ExecuteMsg::RepayFeeDebt {
+            asset: "testtokens".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ANYONE, &[]),
+            execute_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // but the hot wallet can
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(HOT_WALLET, &[]),
+            execute_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "test_repay_address".to_string(),
+                amount: coins(100, "testtokens"),
+            })]
+        );
+
+        // debt is cleared, and the hot wallet's spend limit is untouched
+        let res = query_fee_debt(deps.as_ref()).unwrap();
+        assert_eq!(res.uusd_fee_debt, Uint128::from(0u128));
+        assert_eq!(res.repay_quote, None);
+        let res = query_hot_wallets(deps.as_ref()).unwrap();
+        assert_eq!(
+            res.hot_wallets[0].spend_limits[0].limit_remaining,
+            1_000_000u64
+        );
+
+        // and there is nothing left to repay
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            execute_msg,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoFeeDebt {});
+    }
+
+    #[test]
+    fn lender_adds_fee_debt() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env,
+            Coin {
+                amount: Uint128::from(1_000_000u128),
+                denom: "ibc/EAC38D55372F38F1AFD68DF7FE9EF762DCF69F26520643CF3F9D292A738D8034"
+                    .to_string(),
+            },
+        );
+        let lender_info = mock_info("test_repay_address", &[]);
+        let add_msg = ExecuteMsg::AddFeeDebt {
+            amount: Uint128::from(500_000u128),
+            memo: "gas for tx ABC123".to_string(),
+        };
+
+        // only the lender can add debt
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            add_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::CallerIsNotFeeLender {});
+
+        // and not before admin has set a maximum
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            lender_info.clone(),
+            add_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::FeeDebtOverMaximum("1500000".to_string(), "0".to_string())
+        );
+
+        let max_msg = ExecuteMsg::SetMaxFeeDebt {
+            max_fee_debt: Uint128::from(2_000_000u128),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            lender_info.clone(),
+            max_msg.clone(),
+        )
+        .unwrap_err();
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), max_msg).unwrap();
+
+        // now the lender can add up to the maximum
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            lender_info.clone(),
+            add_msg.clone(),
+        )
+        .unwrap();
+        let res = query_fee_debt(deps.as_ref()).unwrap();
+        assert_eq!(res.uusd_fee_debt, Uint128::from(1_500_000u128));
+        assert_eq!(res.max_fee_debt, Some(Uint128::from(2_000_000u128)));
+        let add_msg = ExecuteMsg::AddFeeDebt {
+            amount: Uint128::from(600_000u128),
+            memo: "gas for tx DEF456".to_string(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), lender_info, add_msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::FeeDebtOverMaximum("2100000".to_string(), "2000000".to_string())
+        );
+
+        // every loan, including the starting debt, is in the history
+        let res = query_fee_debt_history(deps.as_ref(), None, None).unwrap();
+        assert_eq!(res.records.len(), 2);
+        assert_eq!(res.records[0].1.amount, Uint128::from(1_000_000u128));
+        assert_eq!(res.records[0].1.memo, "instantiate".to_string());
+        assert_eq!(res.records[1].1.amount, Uint128::from(500_000u128));
+        assert_eq!(res.records[1].1.memo, "gas for tx ABC123".to_string());
+        let res = query_fee_debt_history(deps.as_ref(), Some(0), Some(1)).unwrap();
+        assert_eq!(res.records.len(), 1);
+        assert_eq!(res.records[0].0, 1);
+    }
+
+    #[test]
+    fn fee_repay_by_deadline() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(1_000_000u128),
+                denom: "ibc/EAC38D55372F38F1AFD68DF7FE9EF762DCF69F26520643CF3F9D292A738D8034"
+                    .to_string(),
+            },
+        );
+        let deadline = current_env.block.time.plus_seconds(86_400);
+        let policy_msg = ExecuteMsg::SetFeeRepayPolicy {
+            policy: FeeRepayPolicy::RepayByDeadline { deadline },
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(HOT_WALLET, &[]),
+            policy_msg.clone(),
+        )
+        .unwrap_err();
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), policy_msg).unwrap();
+
+        // before the deadline, neither admin nor hot wallet has to repay
+        let msgs: Vec<CosmosMsg> = vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: RECEIVER.to_string(),
+            amount: coins(1_000, "testtokens"),
+        })];
+        let execute_msg = ExecuteMsg::Execute { msgs: msgs.clone() };
+        for sender in [ADMIN, HOT_WALLET] {
+            let res = execute(
+                deps.as_mut(),
+                current_env.clone(),
+                mock_info(sender, &[]),
+                execute_msg.clone(),
+            )
+            .unwrap();
+            assert_eq!(
+                res.messages,
+                msgs.clone()
+                    .into_iter()
+                    .map(SubMsg::new)
+                    .collect::<Vec<_>>()
+            );
+        }
+        let res = query_fee_debt(deps.as_ref()).unwrap();
+        assert_eq!(res.uusd_fee_debt, Uint128::from(1_000_000u128));
+
+        // after the deadline, hot wallets are frozen
+        let mut late_env = current_env;
+        late_env.block.time = deadline;
+        let err = execute(
+            deps.as_mut(),
+            late_env.clone(),
+            mock_info(HOT_WALLET, &[]),
+            execute_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::FeeRepayDeadlinePassed {});
+        let res = query_can_spend(
+            deps.as_ref(),
+            late_env.clone(),
+            HOT_WALLET.to_string(),
+            msgs.clone(),
+        )
+        .unwrap();
+        assert!(!res.can_spend);
+
+        // and admin transactions repay in the home asset first
+        let res = execute(
+            deps.as_mut(),
+            late_env.clone(),
+            mock_info(ADMIN, &[]),
+            execute_msg.clone(),
+        )
+        .unwrap();
+        let mut expected_msgs: Vec<CosmosMsg> = vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: "test_repay_address".to_string(),
+            amount: coins(100, "testtokens"),
+        })];
+        expected_msgs.extend(msgs.clone());
+        assert_eq!(
+            res.messages,
+            expected_msgs
+                .into_iter()
+                .map(SubMsg::new)
+                .collect::<Vec<_>>()
+        );
+
+        // which unfreezes the hot wallets
+        execute(
+            deps.as_mut(),
+            late_env,
+            mock_info(HOT_WALLET, &[]),
+            execute_msg,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn fee_repay_on_any_tx() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env,
+            Coin {
+                amount: Uint128::from(1_000_000u128),
+                denom: "ibc/EAC38D55372F38F1AFD68DF7FE9EF762DCF69F26520643CF3F9D292A738D8034"
+                    .to_string(),
+            },
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SetFeeRepayPolicy {
+                policy: FeeRepayPolicy::RepayOnAnyTx,
+            },
+        )
+        .unwrap();
+
+        // even a message that spends nothing repays the debt
+        let staking_msg = CosmosMsg::Staking(StakingMsg::Undelegate {
+            validator: ANYONE.to_string(),
+            amount: coin(70000, "testtokens"),
+        });
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::Execute {
+                msgs: vec![staking_msg.clone()],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: "test_repay_address".to_string(),
+                    amount: coins(100, "testtokens"),
+                }),
+                SubMsg::new(staking_msg),
+            ]
+        );
+        let res = query_fee_debt(deps.as_ref()).unwrap();
+        assert_eq!(res.uusd_fee_debt, Uint128::from(0u128));
+    }
+
     /* #[test]
     fn migrate() {
         let mut deps = mock_dependencies();
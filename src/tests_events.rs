@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coins, BankMsg, Coin, CosmosMsg, Event, Response, Uint128};
+
+    use crate::contract::{execute, execute_execute};
+    use crate::events::{ADMIN_CHANGE_EVENT, FEE_REPAY_EVENT, SPEND_EVENT};
+    use crate::msg::ExecuteMsg;
+    use crate::tests_contract::{ADMIN, HOT_WALLET};
+    use crate::tests_helpers::instantiate_contract;
+
+    const RECEIVER: &str = "diane";
+
+    fn find_event(res: &Response, ty: &str) -> Event {
+        res.events
+            .iter()
+            .find(|event| event.ty == ty)
+            .unwrap_or_else(|| panic!("no {} event in {:?}", ty, res.events))
+            .clone()
+    }
+
+    fn attribute_values(event: &Event, key: &str) -> Vec<String> {
+        event
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn spend_emits_spend_and_fee_repay_events() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(1_000_000u128),
+                denom: "testtokens".to_string(),
+            },
+        );
+        let res = execute_execute(
+            &mut deps.as_mut(),
+            current_env,
+            mock_info(HOT_WALLET, &[]),
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: RECEIVER.to_string(),
+                amount: coins(5_000u128, "testtokens"),
+            })],
+            false,
+        )
+        .unwrap();
+
+        // the first spend repays the fee debt
+        let repay_event = find_event(&res, FEE_REPAY_EVENT);
+        assert_eq!(attribute_values(&repay_event, "wallet"), vec![HOT_WALLET]);
+        assert_eq!(
+            attribute_values(&repay_event, "repay_to"),
+            vec!["test_repay_address"]
+        );
+        assert_eq!(attribute_values(&repay_event, "usd_value"), vec!["1000000"]);
+        assert_eq!(attribute_values(&repay_event, "denom"), vec!["testtokens"]);
+        assert_eq!(attribute_values(&repay_event, "amount"), vec!["100"]);
+        assert!(!attribute_values(&repay_event, "price_source").is_empty());
+
+        let spend_event = find_event(&res, SPEND_EVENT);
+        assert_eq!(attribute_values(&spend_event, "wallet"), vec![HOT_WALLET]);
+        assert_eq!(attribute_values(&spend_event, "usd_value"), vec!["500000"]);
+        assert_eq!(attribute_values(&spend_event, "denom"), vec!["testtokens"]);
+        assert_eq!(attribute_values(&spend_event, "amount"), vec!["5000"]);
+        assert_eq!(
+            attribute_values(&spend_event, "limit_remaining"),
+            vec!["500000"]
+        );
+        assert!(!attribute_values(&spend_event, "price_source").is_empty());
+    }
+
+    #[test]
+    fn admin_change_emits_event() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        let res = execute(
+            deps.as_mut(),
+            current_env,
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::ProposeUpdateAdmin {
+                new_admin: "bob".to_string(),
+            },
+        )
+        .unwrap();
+        let event = find_event(&res, ADMIN_CHANGE_EVENT);
+        assert_eq!(
+            attribute_values(&event, "action"),
+            vec!["propose_update_admin"]
+        );
+        assert_eq!(attribute_values(&event, "wallet"), vec![ADMIN]);
+        assert_eq!(attribute_values(&event, "admin"), vec![ADMIN]);
+        assert_eq!(attribute_values(&event, "new_admin"), vec!["bob"]);
+    }
+
+    #[cfg(feature = "debug-events")]
+    #[test]
+    fn spend_emits_debug_event() {
+        use crate::debug::DEBUG_EVENT;
+
+        let mut deps = mock_dependencies();
+        let mut current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "testtokens".to_string(),
+            },
+        );
+        // just past the hot wallet's first reset
+        let reset = current_env.block.time.seconds();
+        current_env.block.time = current_env.block.time.plus_seconds(1);
+        let res = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::Execute {
+                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: RECEIVER.to_string(),
+                    amount: coins(5_000u128, "testtokens"),
+                })],
+            },
+        )
+        .unwrap();
+        let event = find_event(&res, DEBUG_EVENT);
+        assert_eq!(
+            attribute_values(&event, "pair"),
+            vec!["local_ujuno_to_usdc_fake"]
+        );
+        assert_eq!(attribute_values(&event, "flip_assets"), vec!["false"]);
+        assert_eq!(
+            attribute_values(&event, "old_period_reset"),
+            vec![reset.to_string()]
+        );
+        assert_eq!(
+            attribute_values(&event, "new_period_reset"),
+            vec![(reset + 1 + 86_400).to_string()]
+        );
+    }
+}
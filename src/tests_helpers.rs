@@ -8,6 +8,7 @@ use crate::contract::{execute, execute_execute, instantiate};
 use crate::error::ContractError;
 use crate::hot_wallet::{CoinLimit, HotWallet};
 use crate::msg::{ExecuteMsg, InstantiateMsg};
+use crate::tests_constants::mock_local_pair_query;
 use crate::{contract::query_hot_wallets, hot_wallet::PeriodType};
 
 use crate::tests_contract::{ADMIN, HOT_WALLET};
@@ -17,6 +18,8 @@ pub fn instantiate_contract(
     env: Env,
     starting_debt: Coin,
 ) {
+    // price queries to the local pair contracts get fixed conversions
+    deps.querier.update_wasm(mock_local_pair_query);
     // instantiate the contract
     let instantiate_msg = InstantiateMsg {
         admin: ADMIN.to_string(),
@@ -33,6 +36,11 @@ pub fn instantiate_contract(
             }],
             usdc_denom: Some("true".to_string()),
             default: Some(true),
+            paused: None,
+            max_tx_per_period: None,
+            min_seconds_between_tx: None,
+            tx_count: None,
+            last_tx_time: None,
         }],
         uusd_fee_debt: starting_debt.amount,
         fee_lend_repay_wallet: "test_repay_address".to_string(),
@@ -47,7 +55,7 @@ pub fn instantiate_contract(
     let info = mock_info(ADMIN, &[]);
     let res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
     println!("events: {:?}", res.events);
-    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events.len(), 2);
     assert_eq!(
         res.events[0].attributes[1],
         Attribute::new("signer".to_string(), "testsigner2".to_string())
@@ -79,6 +87,11 @@ pub fn add_test_hotwallet(
             }],
             usdc_denom: Some("true".to_string()),
             default: Some(true),
+            paused: None,
+            max_tx_per_period: None,
+            min_seconds_between_tx: None,
+            tx_count: None,
+            last_tx_time: None,
         },
     };
 
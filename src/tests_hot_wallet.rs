@@ -1,12 +1,17 @@
 #[cfg(test)]
 mod tests {
+    use chrono::{Datelike, NaiveDateTime, Timelike};
     use cosmwasm_std::Timestamp;
+    use proptest::prelude::*;
 
     use crate::{
         constants::MAINNET_AXLUSDC_IBC,
         hot_wallet::{CoinLimit, HotWallet, PeriodType},
     };
 
+    // the latest second a block Timestamp can hold, in 2554
+    const MAX_TEST_TIME: u64 = u64::MAX / 1_000_000_000;
+
     #[test]
     fn hot_wallet_check_is_valid() {
         let mut bad_wallet = HotWallet {
@@ -28,6 +33,11 @@ mod tests {
             ],
             usdc_denom: None,
             default: Some(true),
+            paused: None,
+            max_tx_per_period: None,
+            min_seconds_between_tx: None,
+            tx_count: None,
+            last_tx_time: None,
         };
 
         // multiple limits are no longer supported, so these should error
@@ -61,6 +71,11 @@ mod tests {
             spend_limits: vec![starting_spend_limit.clone()],
             usdc_denom: Some("true".to_string()),
             default: Some(true),
+            paused: None,
+            max_tx_per_period: None,
+            min_seconds_between_tx: None,
+            tx_count: None,
+            last_tx_time: None,
         };
 
         assert_eq!(hot_wallet.spend_limits, vec![starting_spend_limit.clone()]);
@@ -95,6 +110,11 @@ mod tests {
             spend_limits: vec![starting_spend_limit.clone()],
             usdc_denom: Some("true".to_string()),
             default: Some(true),
+            paused: None,
+            max_tx_per_period: None,
+            min_seconds_between_tx: None,
+            tx_count: None,
+            last_tx_time: None,
         };
 
         let adjusted_spend_limit = CoinLimit {
@@ -114,4 +134,89 @@ mod tests {
         assert_eq!(hot_wallet.spend_limits, vec![starting_spend_limit]);
         assert_eq!(hot_wallet.current_period_reset, 1_510_011 + 86_400);
     }
+
+    fn spent_wallet(period_type: PeriodType, period_multiple: u16) -> HotWallet {
+        HotWallet {
+            address: "my_hot_wallet".to_string(),
+            current_period_reset: 0,
+            period_type,
+            period_multiple,
+            spend_limits: vec![CoinLimit {
+                denom: MAINNET_AXLUSDC_IBC.to_string(),
+                amount: 1_000_000u64,
+                limit_remaining: 0u64,
+            }],
+            usdc_denom: Some("true".to_string()),
+            default: Some(true),
+            paused: None,
+            max_tx_per_period: None,
+            min_seconds_between_tx: None,
+            tx_count: None,
+            last_tx_time: None,
+        }
+    }
+
+    fn month_count(seconds: u64) -> i64 {
+        let dt = NaiveDateTime::from_timestamp(seconds as i64, 0);
+        dt.year() as i64 * 12 + dt.month0() as i64
+    }
+
+    #[test]
+    fn hot_wallet_reset_month_edges() {
+        // 2022-11-15T12:00:00
+        let november = Timestamp::from_seconds(1_668_513_600);
+        let mut hot_wallet = spent_wallet(PeriodType::MONTHS, 1);
+        // December used to wrap to month 0 and panic
+        hot_wallet.reset_period(november).unwrap();
+        assert_eq!(hot_wallet.current_period_reset, 1_669_852_800); // 2022-12-01
+        hot_wallet.period_multiple = 13;
+        hot_wallet.reset_period(november).unwrap();
+        assert_eq!(hot_wallet.current_period_reset, 1_701_388_800); // 2023-12-01
+
+        // multiples above 256 used to error
+        hot_wallet.period_multiple = 600;
+        hot_wallet.reset_period(november).unwrap();
+        assert_eq!(month_count(hot_wallet.current_period_reset), 2072 * 12 + 10);
+
+        // a zero period can't reset into the future
+        hot_wallet.period_multiple = 0;
+        hot_wallet.reset_period(november).unwrap_err();
+        hot_wallet.assert_is_valid().unwrap_err();
+        hot_wallet.period_type = PeriodType::DAYS;
+        hot_wallet.reset_period(november).unwrap_err();
+    }
+
+    proptest! {
+        #[test]
+        fn hot_wallet_reset_days_is_in_future(
+            seconds in 0u64..=MAX_TEST_TIME,
+            multiple in 1u16..=u16::MAX,
+        ) {
+            let mut hot_wallet = spent_wallet(PeriodType::DAYS, multiple);
+            prop_assert!(hot_wallet.reset_period(Timestamp::from_seconds(seconds)).is_ok());
+            prop_assert!(hot_wallet.current_period_reset > seconds);
+            prop_assert_eq!(
+                hot_wallet.current_period_reset - seconds,
+                multiple as u64 * 86_400
+            );
+            prop_assert_eq!(hot_wallet.spend_limits[0].limit_remaining, 1_000_000u64);
+        }
+
+        #[test]
+        fn hot_wallet_reset_months_is_first_of_future_month(
+            seconds in 0u64..=MAX_TEST_TIME,
+            multiple in 1u16..=u16::MAX,
+        ) {
+            let mut hot_wallet = spent_wallet(PeriodType::MONTHS, multiple);
+            prop_assert!(hot_wallet.reset_period(Timestamp::from_seconds(seconds)).is_ok());
+            let reset = hot_wallet.current_period_reset;
+            prop_assert!(reset > seconds);
+            let reset_dt = NaiveDateTime::from_timestamp_opt(reset as i64, 0);
+            prop_assert!(reset_dt.is_some());
+            let reset_dt = reset_dt.unwrap();
+            prop_assert_eq!((reset_dt.day(), reset_dt.num_seconds_from_midnight()), (1, 0));
+            prop_assert_eq!(month_count(reset) - month_count(seconds), multiple as i64);
+            prop_assert_eq!(hot_wallet.spend_limits[0].limit_remaining, 1_000_000u64);
+        }
+    }
 }
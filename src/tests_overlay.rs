@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::{Order, Storage};
+
+    use crate::overlay::OverlayStorage;
+
+    #[test]
+    fn overlay_keeps_writes_from_base() {
+        let mut base = MockStorage::new();
+        base.set(b"a", b"1");
+        base.set(b"b", b"2");
+        base.set(b"c", b"3");
+
+        let mut overlay = OverlayStorage::new(&base);
+        overlay.set(b"b", b"20");
+        overlay.remove(b"c");
+        overlay.set(b"d", b"4");
+        assert_eq!(overlay.get(b"a"), Some(b"1".to_vec()));
+        assert_eq!(overlay.get(b"b"), Some(b"20".to_vec()));
+        assert_eq!(overlay.get(b"c"), None);
+
+        let keys: Vec<Vec<u8>> = overlay
+            .range(None, None, Order::Ascending)
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(keys, vec![b"a".to_vec(), b"b".to_vec(), b"d".to_vec()]);
+        let records: Vec<_> = overlay
+            .range(Some(b"b"), Some(b"d"), Order::Descending)
+            .collect();
+        assert_eq!(records, vec![(b"b".to_vec(), b"20".to_vec())]);
+
+        // base is untouched
+        assert_eq!(base.get(b"b"), Some(b"2".to_vec()));
+        assert_eq!(base.get(b"c"), Some(b"3".to_vec()));
+        assert_eq!(base.get(b"d"), None);
+    }
+
+    #[test]
+    fn overlay_writes_apply_to_base() {
+        let mut base = MockStorage::new();
+        base.set(b"a", b"1");
+        base.set(b"b", b"2");
+
+        let mut overlay = OverlayStorage::new(&base);
+        overlay.set(b"a", b"10");
+        overlay.remove(b"b");
+        overlay.set(b"c", b"3");
+        let writes = overlay.into_writes();
+        assert_eq!(base.get(b"a"), Some(b"1".to_vec()));
+
+        writes.apply_to(&mut base);
+        assert_eq!(base.get(b"a"), Some(b"10".to_vec()));
+        assert_eq!(base.get(b"b"), None);
+        assert_eq!(base.get(b"c"), Some(b"3".to_vec()));
+    }
+}
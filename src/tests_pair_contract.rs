@@ -1,13 +1,86 @@
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::Uint128;
+    use cosmwasm_std::testing::{mock_dependencies, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_binary, Coin, OwnedDeps, SystemResult, Uint128, WasmQuery};
 
     use crate::{
         constants::{MAINNET_AXLUSDC_IBC, MAINNET_DENOM, MAINNET_DEX_DENOM},
+        mock_pair::MockPair,
         pair_contract::{PairContract, PairMessageType},
         simulation::{DexQueryMsg, DexQueryMsgType, FormatQueryMsg},
+        ContractError,
     };
 
+    const PAIR_ADDR: &str = "mock_pair";
+
+    fn mock_pair_deps(pair: MockPair) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                SystemResult::Ok(from_binary(msg).and_then(|msg| pair.respond(&msg)).into())
+            }
+            _ => panic!("unexpected query {:?}", query),
+        });
+        deps
+    }
+
+    fn mock_pair(query_format: PairMessageType, denom2: &str) -> MockPair {
+        MockPair {
+            query_format,
+            denom1: "testtokens".to_string(),
+            denom2: denom2.to_string(),
+            reserve1: Uint128::from(1_000_000_000u128),
+            reserve2: Uint128::from(2_000_000_000u128),
+        }
+    }
+
+    fn pair_contract(query_format: PairMessageType, denom2: &str) -> PairContract {
+        PairContract {
+            contract_addr: PAIR_ADDR.to_string(),
+            denom1: "testtokens".to_string(),
+            denom2: denom2.to_string(),
+            query_format,
+        }
+    }
+
+    #[test]
+    fn pair_contract_queries_mock_pairs() {
+        // junoswap answers with the other token's amount
+        let deps = mock_pair_deps(mock_pair(PairMessageType::JunoType, MAINNET_AXLUSDC_IBC));
+        let res = pair_contract(PairMessageType::JunoType, MAINNET_AXLUSDC_IBC)
+            .query_contract(deps.as_ref(), Uint128::from(1_000u128), false, false, false)
+            .unwrap();
+        assert_eq!(res.coin, Coin::new(1_999u128, MAINNET_AXLUSDC_IBC));
+        assert_eq!(res.wrapped_sources.sources[0].contract_addr, PAIR_ADDR);
+        let res = pair_contract(PairMessageType::JunoType, MAINNET_AXLUSDC_IBC)
+            .query_contract(deps.as_ref(), Uint128::from(1_000u128), true, false, false)
+            .unwrap();
+        assert_eq!(res.coin, Coin::new(499u128, "testtokens"));
+
+        // loop's commission is tallied back into the value
+        let deps = mock_pair_deps(mock_pair(PairMessageType::LoopType, MAINNET_DEX_DENOM));
+        let res = pair_contract(PairMessageType::LoopType, MAINNET_DEX_DENOM)
+            .query_contract(deps.as_ref(), Uint128::from(1_000u128), false, false, false)
+            .unwrap();
+        assert_eq!(res.coin, Coin::new(1_999u128, MAINNET_DEX_DENOM));
+        // reversed, loop is asked what must be offered for the amount
+        let res = pair_contract(PairMessageType::LoopType, MAINNET_DEX_DENOM)
+            .query_contract(deps.as_ref(), Uint128::from(1_000u128), true, false, false)
+            .unwrap();
+        assert_eq!(res.coin, Coin::new(2_000u128, MAINNET_DEX_DENOM));
+
+        // a pair that can't answer the query fails the price check
+        let err = pair_contract(PairMessageType::JunoType, MAINNET_DEX_DENOM)
+            .query_contract(deps.as_ref(), Uint128::from(1_000u128), false, false, false)
+            .unwrap_err();
+        match err {
+            ContractError::PriceCheckFailed(_, contract_addr, _) => {
+                assert_eq!(contract_addr, PAIR_ADDR)
+            }
+            _ => panic!("unexpected error {:?}", err),
+        }
+    }
+
     #[test]
     fn pair_contract_get_denoms() {
         let test_pair_contract = PairContract {
@@ -0,0 +1,212 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coins, Addr, BankMsg, Coin, CosmosMsg, StakingMsg, Timestamp, Uint128};
+
+    use crate::contract::{execute, query_proposal, query_proposals};
+    use crate::msg::ExecuteMsg;
+    use crate::proposal::Proposal;
+    use crate::submsgs::SubmsgType;
+    use crate::tests_contract::{ADMIN, HOT_WALLET};
+    use crate::tests_helpers::instantiate_contract;
+    use crate::ContractError;
+
+    const SIGNER_1: &str = "testsigner1";
+    const SIGNER_2: &str = "testsigner2";
+    const RECEIVER: &str = "receiver";
+
+    fn big_send() -> CosmosMsg {
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: RECEIVER.to_string(),
+            amount: coins(1_000_000_000u128, "testtokens"),
+        })
+    }
+
+    #[test]
+    fn proposal_approvals_and_expiry() {
+        let mut proposal = Proposal {
+            proposer: Addr::unchecked(SIGNER_1),
+            msgs: vec![big_send()],
+            msg_types: vec![SubmsgType::BankSend],
+            spend: coins(1_000_000_000u128, "testtokens"),
+            approvals: vec![Addr::unchecked(SIGNER_1)],
+            expires: Timestamp::from_seconds(1_000),
+            executed: false,
+        };
+
+        // approving twice doesn't count twice
+        proposal.approve(Addr::unchecked(SIGNER_1));
+        assert_eq!(
+            proposal
+                .assert_is_executable(2, Timestamp::from_seconds(999))
+                .unwrap_err(),
+            ContractError::ProposalNotApproved(1, 2)
+        );
+        proposal.approve(Addr::unchecked(SIGNER_2));
+        proposal
+            .assert_is_executable(2, Timestamp::from_seconds(999))
+            .unwrap();
+        assert_eq!(
+            proposal
+                .assert_is_executable(2, Timestamp::from_seconds(1_000))
+                .unwrap_err(),
+            ContractError::ProposalExpired {}
+        );
+
+        proposal.executed = true;
+        assert_eq!(
+            proposal
+                .assert_is_open(Timestamp::from_seconds(999))
+                .unwrap_err(),
+            ContractError::ProposalAlreadyExecuted {}
+        );
+    }
+
+    #[test]
+    fn signers_approve_large_spend() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        let expires = current_env.block.time.plus_seconds(3_600);
+        let propose_msg = ExecuteMsg::ProposeExecute {
+            msgs: vec![big_send()],
+            expires,
+        };
+
+        // proposals must be enabled by admin first
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(SIGNER_1, &[]),
+            propose_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ProposalsNotEnabled {});
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SetProposalThreshold { threshold: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidProposalThreshold {});
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SetProposalThreshold { threshold: 2 },
+        )
+        .unwrap();
+
+        // only signers and hot wallets propose, and only spends
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(RECEIVER, &[]),
+            propose_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(SIGNER_1, &[]),
+            ExecuteMsg::ProposeExecute {
+                msgs: vec![CosmosMsg::Staking(StakingMsg::Delegate {
+                    validator: "validator".to_string(),
+                    amount: Coin::new(1_000u128, "testtokens"),
+                })],
+                expires,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BadMessageType("unknown".to_string()));
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(SIGNER_1, &[]),
+            propose_msg,
+        )
+        .unwrap();
+        let res = query_proposal(deps.as_ref(), current_env.clone(), 0).unwrap();
+        assert_eq!(res.proposal.spend, coins(1_000_000_000u128, "testtokens"));
+        assert!(!res.executable);
+
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(RECEIVER, &[]),
+            ExecuteMsg::ExecuteProposal { id: 0 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ProposalNotApproved(1, 2));
+
+        // a hot wallet's approval counts too
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::ApproveProposal { id: 0 },
+        )
+        .unwrap();
+        let res = query_proposals(deps.as_ref(), current_env.clone(), None, None).unwrap();
+        assert_eq!(res.proposals.len(), 1);
+        assert!(res.proposals[0].executable);
+
+        // but not once the hot wallet is paused
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::PauseHotWallet {
+                address: HOT_WALLET.to_string(),
+            },
+        )
+        .unwrap();
+        let res = query_proposal(deps.as_ref(), current_env.clone(), 0).unwrap();
+        assert!(!res.executable);
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(RECEIVER, &[]),
+            ExecuteMsg::ExecuteProposal { id: 0 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ProposalNotApproved(1, 2));
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::UnpauseHotWallet {
+                address: HOT_WALLET.to_string(),
+            },
+        )
+        .unwrap();
+
+        // anyone can execute once approved, and only once
+        let res = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(RECEIVER, &[]),
+            ExecuteMsg::ExecuteProposal { id: 0 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, big_send());
+        let err = execute(
+            deps.as_mut(),
+            current_env,
+            mock_info(RECEIVER, &[]),
+            ExecuteMsg::ExecuteProposal { id: 0 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ProposalAlreadyExecuted {});
+    }
+}
@@ -0,0 +1,464 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Addr, Attribute, Coin, DepsMut, Env, Response, Timestamp, Uint128};
+
+    use crate::contract::{
+        execute, query_admin, query_guardians, query_heir, query_pending_admin,
+        query_recovery_status,
+    };
+    use crate::msg::ExecuteMsg;
+    use crate::recovery::{GuardianSet, Recovery};
+    use crate::tests_contract::ADMIN;
+    use crate::tests_helpers::instantiate_contract;
+    use crate::ContractError;
+
+    const GUARDIAN_1: &str = "guardian_one";
+    const GUARDIAN_2: &str = "guardian_two";
+    const GUARDIAN_3: &str = "guardian_three";
+    const NEW_ADMIN: &str = "bob";
+    const DAY: u64 = 86_400;
+
+    #[test]
+    fn guardian_set_check_is_valid() {
+        let mut guardian_set = GuardianSet {
+            guardians: vec![Addr::unchecked(GUARDIAN_1), Addr::unchecked(GUARDIAN_2)],
+            threshold: 0,
+            recovery_delay: 86_400,
+        };
+        guardian_set.assert_is_valid().unwrap_err();
+        guardian_set.threshold = 3;
+        guardian_set.assert_is_valid().unwrap_err();
+        guardian_set.threshold = 2;
+        guardian_set.assert_is_valid().unwrap();
+
+        assert!(guardian_set.is_guardian(GUARDIAN_1.to_string()));
+        assert!(!guardian_set.is_guardian(GUARDIAN_3.to_string()));
+    }
+
+    #[test]
+    fn recovery_needs_threshold_and_delay() {
+        let guardian_set = GuardianSet {
+            guardians: vec![Addr::unchecked(GUARDIAN_1), Addr::unchecked(GUARDIAN_2)],
+            threshold: 2,
+            recovery_delay: 100,
+        };
+        let mut recovery = Recovery {
+            new_admin: Addr::unchecked(NEW_ADMIN),
+            approvals: vec![Addr::unchecked(GUARDIAN_1)],
+            proposed_at: Timestamp::from_seconds(1_000),
+            executable_at: Timestamp::from_seconds(1_100),
+            expires_at: Timestamp::from_seconds(1_200),
+            rivals: vec![],
+        };
+
+        // approving twice doesn't count twice
+        recovery.approve(Addr::unchecked(GUARDIAN_1));
+        assert_eq!(
+            recovery
+                .assert_is_executable(&guardian_set, Timestamp::from_seconds(1_100))
+                .unwrap_err(),
+            ContractError::RecoveryNotApproved(1, 2)
+        );
+
+        recovery.approve(Addr::unchecked(GUARDIAN_2));
+        assert_eq!(
+            recovery
+                .assert_is_executable(&guardian_set, Timestamp::from_seconds(1_099))
+                .unwrap_err(),
+            ContractError::RecoveryTimelocked(1_100)
+        );
+        recovery
+            .assert_is_executable(&guardian_set, Timestamp::from_seconds(1_100))
+            .unwrap();
+        assert_eq!(
+            recovery
+                .assert_is_executable(&guardian_set, Timestamp::from_seconds(1_200))
+                .unwrap_err(),
+            ContractError::RecoveryExpired(1_200)
+        );
+    }
+
+    fn set_guardians(deps: DepsMut) {
+        execute(
+            deps,
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SetGuardians {
+                guardians: vec![
+                    GUARDIAN_1.to_string(),
+                    GUARDIAN_2.to_string(),
+                    GUARDIAN_3.to_string(),
+                ],
+                threshold: 2,
+                recovery_delay: DAY,
+            },
+        )
+        .unwrap();
+    }
+
+    fn propose(deps: DepsMut, env: Env, guardian: &str, new_admin: &str) -> Response {
+        execute(
+            deps,
+            env,
+            mock_info(guardian, &[]),
+            ExecuteMsg::ProposeRecovery {
+                new_admin: new_admin.to_string(),
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn guardian_threshold_replaces_rogue_recovery() {
+        let mut deps = mock_dependencies();
+        instantiate_contract(
+            &mut deps,
+            mock_env(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        set_guardians(deps.as_mut());
+
+        // a rogue guardian proposes itself
+        propose(deps.as_mut(), env_at(0), GUARDIAN_1, GUARDIAN_1);
+        let err = execute(
+            deps.as_mut(),
+            env_at(0),
+            mock_info(GUARDIAN_1, &[]),
+            ExecuteMsg::ProposeRecovery {
+                new_admin: GUARDIAN_1.to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::RecoveryAlreadyProposed {});
+
+        // one guardian backing a rival doesn't displace it
+        propose(deps.as_mut(), env_at(0), GUARDIAN_2, NEW_ADMIN);
+        let recovery = query_recovery_status(deps.as_ref(), env_at(0))
+            .unwrap()
+            .recovery
+            .unwrap();
+        assert_eq!(recovery.new_admin, Addr::unchecked(GUARDIAN_1));
+        assert_eq!(recovery.rivals.len(), 1);
+
+        // a threshold backing the same rival does, and restarts the delay
+        propose(deps.as_mut(), env_at(1), GUARDIAN_3, NEW_ADMIN);
+        let recovery = query_recovery_status(deps.as_ref(), env_at(1))
+            .unwrap()
+            .recovery
+            .unwrap();
+        assert_eq!(recovery.new_admin, Addr::unchecked(NEW_ADMIN));
+        assert_eq!(
+            recovery.approvals,
+            vec![Addr::unchecked(GUARDIAN_2), Addr::unchecked(GUARDIAN_3)]
+        );
+        assert_eq!(recovery.executable_at, env_at(2).block.time);
+        assert!(recovery.rivals.is_empty());
+
+        execute(
+            deps.as_mut(),
+            env_at(2),
+            mock_info(GUARDIAN_1, &[]),
+            ExecuteMsg::ExecuteRecovery {},
+        )
+        .unwrap();
+        assert_eq!(
+            query_admin(deps.as_ref()).unwrap().admin,
+            NEW_ADMIN.to_string()
+        );
+    }
+
+    #[test]
+    fn expired_recovery_can_be_proposed_afresh() {
+        let mut deps = mock_dependencies();
+        instantiate_contract(
+            &mut deps,
+            mock_env(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        set_guardians(deps.as_mut());
+
+        propose(deps.as_mut(), env_at(0), GUARDIAN_1, GUARDIAN_1);
+        let expired = env_at(15);
+        let res = query_recovery_status(deps.as_ref(), expired.clone()).unwrap();
+        assert!(!res.executable);
+        let err = execute(
+            deps.as_mut(),
+            expired.clone(),
+            mock_info(GUARDIAN_2, &[]),
+            ExecuteMsg::ApproveRecovery {},
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RecoveryExpired(env_at(15).block.time.seconds())
+        );
+
+        // a single guardian replaces an expired recovery
+        propose(deps.as_mut(), expired.clone(), GUARDIAN_2, NEW_ADMIN);
+        let recovery = query_recovery_status(deps.as_ref(), expired)
+            .unwrap()
+            .recovery
+            .unwrap();
+        assert_eq!(recovery.new_admin, Addr::unchecked(NEW_ADMIN));
+        assert_eq!(recovery.approvals, vec![Addr::unchecked(GUARDIAN_2)]);
+    }
+
+    #[test]
+    fn guardians_recover_admin() {
+        let mut deps = mock_dependencies();
+        let current_env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            current_env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+
+        // only admin sets guardians
+        let set_msg = ExecuteMsg::SetGuardians {
+            guardians: vec![
+                GUARDIAN_1.to_string(),
+                GUARDIAN_2.to_string(),
+                GUARDIAN_3.to_string(),
+            ],
+            threshold: 2,
+            recovery_delay: 86_400,
+        };
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(GUARDIAN_1, &[]),
+            set_msg.clone(),
+        )
+        .unwrap_err();
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            set_msg,
+        )
+        .unwrap();
+        let res = query_guardians(deps.as_ref()).unwrap();
+        assert_eq!(res.guardians.unwrap().threshold, 2);
+
+        // only guardians propose
+        let propose_msg = ExecuteMsg::ProposeRecovery {
+            new_admin: NEW_ADMIN.to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(NEW_ADMIN, &[]),
+            propose_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::CallerIsNotGuardian {});
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(GUARDIAN_1, &[]),
+            propose_msg.clone(),
+        )
+        .unwrap();
+
+        // admin can veto during the delay
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::VetoRecovery {},
+        )
+        .unwrap();
+        let res = query_recovery_status(deps.as_ref(), current_env.clone()).unwrap();
+        assert_eq!(res.recovery, None);
+
+        // propose again; with one approval and before the delay, cannot execute
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(GUARDIAN_1, &[]),
+            propose_msg,
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(NEW_ADMIN, &[]),
+            ExecuteMsg::ExecuteRecovery {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::RecoveryNotApproved(1, 2));
+
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(GUARDIAN_3, &[]),
+            ExecuteMsg::ApproveRecovery {},
+        )
+        .unwrap();
+        let res = query_recovery_status(deps.as_ref(), current_env.clone()).unwrap();
+        assert_eq!(res.recovery.unwrap().approvals.len(), 2);
+        assert!(!res.executable);
+        execute(
+            deps.as_mut(),
+            current_env.clone(),
+            mock_info(NEW_ADMIN, &[]),
+            ExecuteMsg::ExecuteRecovery {},
+        )
+        .unwrap_err();
+
+        // once the delay has passed, anyone can execute it
+        let mut later_env = current_env;
+        later_env.block.time = later_env.block.time.plus_seconds(86_400);
+        let res = query_recovery_status(deps.as_ref(), later_env.clone()).unwrap();
+        assert!(res.executable);
+        execute(
+            deps.as_mut(),
+            later_env.clone(),
+            mock_info(NEW_ADMIN, &[]),
+            ExecuteMsg::ExecuteRecovery {},
+        )
+        .unwrap();
+        assert_eq!(
+            query_admin(deps.as_ref()).unwrap().admin,
+            NEW_ADMIN.to_string()
+        );
+        let res = query_recovery_status(deps.as_ref(), later_env).unwrap();
+        assert_eq!(res.recovery, None);
+    }
+
+    fn env_at(days: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(days * DAY);
+        env
+    }
+
+    fn claim(deps: DepsMut, days: u64) -> Result<Response, ContractError> {
+        execute(
+            deps,
+            env_at(days),
+            mock_info(NEW_ADMIN, &[]),
+            ExecuteMsg::ClaimInheritance {},
+        )
+    }
+
+    #[test]
+    fn heir_claims_admin_after_inactivity() {
+        let mut deps = mock_dependencies();
+        let start = mock_env().block.time;
+        instantiate_contract(
+            &mut deps,
+            mock_env(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        let set_heir_msg = ExecuteMsg::SetHeir {
+            heir: Some(NEW_ADMIN.to_string()),
+            inactivity_seconds: 30 * DAY,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env_at(0),
+            mock_info(NEW_ADMIN, &[]),
+            set_heir_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            env_at(0),
+            mock_info(ADMIN, &[]),
+            set_heir_msg,
+        )
+        .unwrap();
+
+        // any admin transaction pushes the claim back
+        let err = claim(deps.as_mut(), 29).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InheritanceNotClaimable(start.plus_seconds(30 * DAY).seconds())
+        );
+        execute(
+            deps.as_mut(),
+            env_at(20),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SetAdminTransferDelay {
+                delay_seconds: 3 * DAY,
+            },
+        )
+        .unwrap();
+        claim(deps.as_mut(), 40).unwrap_err();
+        assert!(!query_heir(deps.as_ref(), env_at(49)).unwrap().claimable);
+        assert!(query_heir(deps.as_ref(), env_at(50)).unwrap().claimable);
+
+        // the claim waits out the week's grace, longer than the delay,
+        // and a heartbeat cancels it
+        claim(deps.as_mut(), 50).unwrap();
+        let res = query_pending_admin(deps.as_ref()).unwrap();
+        assert_eq!(res.pending, Some(NEW_ADMIN.to_string()));
+        assert_eq!(res.executable_at, Some(start.plus_seconds(57 * DAY)));
+        let confirm_msg = ExecuteMsg::ConfirmUpdateAdmin { signers: vec![] };
+        execute(
+            deps.as_mut(),
+            env_at(56),
+            mock_info(NEW_ADMIN, &[]),
+            confirm_msg.clone(),
+        )
+        .unwrap_err();
+        execute(
+            deps.as_mut(),
+            env_at(56),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::Heartbeat {},
+        )
+        .unwrap();
+        assert_eq!(query_pending_admin(deps.as_ref()).unwrap().pending, None);
+        claim(deps.as_mut(), 57).unwrap_err();
+
+        // so does any other admin transaction
+        claim(deps.as_mut(), 86).unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env_at(87),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SetAdminTransferDelay {
+                delay_seconds: 3 * DAY,
+            },
+        )
+        .unwrap();
+        assert!(res.events.iter().any(|event| event
+            .attributes
+            .contains(&Attribute::new("action", "cancel_inheritance"))));
+        assert_eq!(query_pending_admin(deps.as_ref()).unwrap().pending, None);
+        execute(
+            deps.as_mut(),
+            env_at(94),
+            mock_info(NEW_ADMIN, &[]),
+            confirm_msg.clone(),
+        )
+        .unwrap_err();
+
+        // left alone, the heir becomes admin, and the heir is cleared
+        claim(deps.as_mut(), 117).unwrap();
+        execute(
+            deps.as_mut(),
+            env_at(124),
+            mock_info(NEW_ADMIN, &[]),
+            confirm_msg,
+        )
+        .unwrap();
+        assert_eq!(query_admin(deps.as_ref()).unwrap().admin, NEW_ADMIN);
+        assert_eq!(query_heir(deps.as_ref(), env_at(124)).unwrap().heir, None);
+    }
+}
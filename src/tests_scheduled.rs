@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coins, BankMsg, Coin, CosmosMsg, StakingMsg, Uint128};
+
+    use crate::contract::{execute, query_scheduled_transfers};
+    use crate::msg::ExecuteMsg;
+    use crate::submsgs::SubmsgType;
+    use crate::tests_contract::{ADMIN, HOT_WALLET};
+    use crate::tests_helpers::instantiate_contract;
+    use crate::ContractError;
+
+    const VENDOR: &str = "vendor";
+    const KEEPER: &str = "keeper";
+
+    fn payment() -> CosmosMsg {
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: VENDOR.to_string(),
+            amount: coins(50_000, "testtokens"),
+        })
+    }
+
+    #[test]
+    fn scheduled_transfer_waits_until_matured() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        let execute_after = env.block.time.plus_seconds(86_400);
+        let schedule_msg = ExecuteMsg::ScheduleTransfer {
+            msg: payment(),
+            execute_after,
+        };
+
+        // only admin schedules, only in the future, and only transfers
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(HOT_WALLET, &[]),
+            schedule_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::ScheduleTransfer {
+                msg: payment(),
+                execute_after: env.block.time,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ScheduledTransferInPast {});
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::ScheduleTransfer {
+                msg: CosmosMsg::Staking(StakingMsg::Delegate {
+                    validator: "validator".to_string(),
+                    amount: Coin::new(50_000, "testtokens"),
+                }),
+                execute_after,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BadMessageType("unknown".to_string()));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            schedule_msg.clone(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            schedule_msg,
+        )
+        .unwrap();
+        let res = query_scheduled_transfers(deps.as_ref(), None, None).unwrap();
+        assert_eq!(res.transfers.len(), 2);
+        assert_eq!(res.transfers[0].1.msg_type, SubmsgType::BankSend);
+        assert_eq!(res.transfers[0].1.spend, coins(50_000, "testtokens"));
+
+        // admin cancels the first before it matures
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER, &[]),
+            ExecuteMsg::ExecuteScheduledTransfer { id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ScheduledTransferNotMatured(execute_after.seconds())
+        );
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::CancelScheduledTransfer { id: 0 },
+        )
+        .unwrap();
+
+        // then anyone sends the second, which admin can no longer cancel
+        env.block.time = execute_after;
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::CancelScheduledTransfer { id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ScheduledTransferMatured {});
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER, &[]),
+            ExecuteMsg::ExecuteScheduledTransfer { id: 1 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, payment());
+        let err = execute(
+            deps.as_mut(),
+            env,
+            mock_info(KEEPER, &[]),
+            ExecuteMsg::ExecuteScheduledTransfer { id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ScheduledTransferDoesNotExist {});
+        let res = query_scheduled_transfers(deps.as_ref(), None, None).unwrap();
+        assert!(res.transfers.is_empty());
+    }
+}
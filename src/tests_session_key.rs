@@ -0,0 +1,314 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier};
+    use cosmwasm_std::{
+        coin, coins, to_binary, BankMsg, Binary, Coin, CosmosMsg, Empty, MemoryStorage, OwnedDeps,
+        SubMsg, Uint128, WasmMsg,
+    };
+    use serde::Serialize;
+
+    use crate::contract::{execute, query_can_execute, query_fee_debt, query_session_keys};
+    use crate::events::SESSION_KEY_EVENT;
+    use crate::msg::ExecuteMsg;
+    use crate::session_key::SessionKey;
+    use crate::tests_contract::{ADMIN, HOT_WALLET};
+    use crate::tests_helpers::instantiate_contract;
+    use crate::ContractError;
+
+    const SESSION_KEY: &str = "sessionsam";
+    const GAME: &str = "gamecontract";
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "snake_case")]
+    enum GameMsg {
+        Play { round: u64 },
+        Withdraw {},
+    }
+
+    type MockDeps = OwnedDeps<MemoryStorage, MockApi, MockQuerier<Empty>, Empty>;
+
+    fn session_key() -> SessionKey {
+        SessionKey {
+            address: SESSION_KEY.to_string(),
+            contract: GAME.to_string(),
+            allowed_msgs: vec!["play".to_string()],
+            // 1 USDC, or 10,000 testtokens
+            usd_cap: 1_000_000,
+            usd_spent: 0,
+            expires: mock_env().block.time.plus_seconds(3 * 3_600),
+        }
+    }
+
+    fn game_msg(contract: &str, msg: &GameMsg, amount: u128) -> CosmosMsg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_binary(msg).unwrap(),
+            funds: coins(amount, "testtokens"),
+        })
+    }
+
+    fn setup() -> MockDeps {
+        let mut deps = mock_dependencies();
+        instantiate_contract(
+            &mut deps,
+            mock_env(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::CreateSessionKey {
+                session_key: session_key(),
+            },
+        )
+        .unwrap();
+        deps
+    }
+
+    #[test]
+    fn admin_creates_and_revokes_session_keys() {
+        let mut deps = setup();
+        let create_msg = ExecuteMsg::CreateSessionKey {
+            session_key: session_key(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(HOT_WALLET, &[]),
+            create_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err =
+            execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), create_msg).unwrap_err();
+        assert_eq!(err, ContractError::SessionKeyExists {});
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::CreateSessionKey {
+                session_key: SessionKey {
+                    address: HOT_WALLET.to_string(),
+                    ..session_key()
+                },
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::SessionKeyExists {});
+        let res = query_session_keys(deps.as_ref()).unwrap();
+        assert_eq!(res.session_keys, vec![session_key()]);
+
+        // a session key can revoke itself
+        let revoke_msg = ExecuteMsg::RevokeSessionKey {
+            address: SESSION_KEY.to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(HOT_WALLET, &[]),
+            revoke_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(SESSION_KEY, &[]),
+            revoke_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.events[0].ty, SESSION_KEY_EVENT);
+        let err =
+            execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), revoke_msg).unwrap_err();
+        assert_eq!(err, ContractError::SessionKeyDoesNotExist {});
+        let res = query_session_keys(deps.as_ref()).unwrap();
+        assert!(res.session_keys.is_empty());
+    }
+
+    #[test]
+    fn session_key_reads_single_msg_name() {
+        let key = session_key();
+        let raw_msg = |json: &str| {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: GAME.to_string(),
+                msg: Binary::from(json.as_bytes()),
+                funds: vec![],
+            })
+        };
+        key.check_msg(&raw_msg(r#" { "play" : {"round": 1, "memo": "\"}"}} "#))
+            .unwrap();
+        // a second key could smuggle in another message
+        assert_eq!(
+            key.check_msg(&raw_msg(r#"{"play": {}, "withdraw": {}}"#))
+                .unwrap_err(),
+            ContractError::SessionKeyMsgNotAllowed(String::new())
+        );
+        assert_eq!(
+            key.check_msg(&raw_msg(r#"{"pl\"ay": {}}"#)).unwrap_err(),
+            ContractError::SessionKeyMsgNotAllowed("pl\"ay".to_string())
+        );
+
+        // spends too large for a u64 are over the cap, not truncated
+        let mut key = session_key();
+        let err = key.spend(Uint128::from(u64::MAX as u128 + 1)).unwrap_err();
+        assert!(matches!(err, ContractError::SessionKeyOverCap(..)));
+        assert_eq!(key.usd_spent, 0);
+    }
+
+    #[test]
+    fn session_key_only_reaches_its_contract() {
+        let mut deps = setup();
+        let session_info = mock_info(SESSION_KEY, &[]);
+
+        // queries answer for session keys the same as Execute
+        let play = game_msg(GAME, &GameMsg::Play { round: 1 }, 4_000);
+        let res =
+            query_can_execute(deps.as_ref(), mock_env(), SESSION_KEY.to_string(), play).unwrap();
+        assert!(res.can_execute);
+        let withdraw = game_msg(GAME, &GameMsg::Withdraw {}, 0);
+        let res = query_can_execute(deps.as_ref(), mock_env(), SESSION_KEY.to_string(), withdraw)
+            .unwrap();
+        assert!(!res.can_execute);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            session_info.clone(),
+            ExecuteMsg::Execute {
+                msgs: vec![game_msg(GAME, &GameMsg::Play { round: 1 }, 4_000)],
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let res = query_session_keys(deps.as_ref()).unwrap();
+        assert_eq!(res.session_keys[0].usd_spent, 400_000);
+
+        // other messages, contracts and bank sends are rejected
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            session_info.clone(),
+            ExecuteMsg::Execute {
+                msgs: vec![game_msg(GAME, &GameMsg::Withdraw {}, 0)],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::SessionKeyMsgNotAllowed("withdraw".to_string())
+        );
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            session_info.clone(),
+            ExecuteMsg::Execute {
+                msgs: vec![game_msg("othergame", &GameMsg::Play { round: 1 }, 0)],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::SessionKeyWrongContract(GAME.to_string())
+        );
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            session_info.clone(),
+            ExecuteMsg::Execute {
+                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: GAME.to_string(),
+                    amount: coins(1, "testtokens"),
+                })],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::SessionKeyWrongContract(GAME.to_string())
+        );
+
+        // the cap covers the whole session
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            session_info.clone(),
+            ExecuteMsg::Execute {
+                msgs: vec![
+                    game_msg(GAME, &GameMsg::Play { round: 2 }, 4_000),
+                    game_msg(GAME, &GameMsg::Play { round: 3 }, 4_000),
+                ],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::SessionKeyOverCap("1200000".to_string(), "1000000".to_string())
+        );
+
+        // and it stops working once expired
+        let mut env = mock_env();
+        env.block.time = session_key().expires;
+        let err = execute(
+            deps.as_mut(),
+            env,
+            session_info,
+            ExecuteMsg::Execute {
+                msgs: vec![game_msg(GAME, &GameMsg::Play { round: 2 }, 0)],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::SessionKeyExpired {});
+    }
+
+    #[test]
+    fn session_key_spend_repays_fee_debt_first() {
+        let mut deps = mock_dependencies();
+        instantiate_contract(
+            &mut deps,
+            mock_env(),
+            Coin {
+                amount: Uint128::from(1_000_000u128),
+                denom: "testtokens".to_string(),
+            },
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::CreateSessionKey {
+                session_key: session_key(),
+            },
+        )
+        .unwrap();
+        let repay_wallet = query_fee_debt(deps.as_ref()).unwrap().fee_lend_repay_wallet;
+
+        let play = game_msg(GAME, &GameMsg::Play { round: 1 }, 4_000);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(SESSION_KEY, &[]),
+            ExecuteMsg::Execute {
+                msgs: vec![play.clone()],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: repay_wallet,
+                    amount: vec![coin(100, "testtokens")],
+                }),
+                SubMsg::new(play),
+            ]
+        );
+        assert_eq!(
+            query_fee_debt(deps.as_ref()).unwrap().uusd_fee_debt,
+            Uint128::from(0u128)
+        );
+    }
+}
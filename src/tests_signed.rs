@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier};
+    use cosmwasm_std::{
+        coins, to_vec, BankMsg, Binary, Coin, CosmosMsg, Empty, Env, MemoryStorage, OwnedDeps,
+        Response, Uint128,
+    };
+    use k256::ecdsa::signature::Signer;
+    use k256::ecdsa::{Signature, SigningKey};
+
+    use crate::contract::{execute, query_fee_debt, query_fee_debt_history, query_signed_nonce};
+    use crate::events::SIGNED_EXECUTE_EVENT;
+    use crate::msg::ExecuteMsg;
+    use crate::signed::SignedPayload;
+    use crate::tests_contract::{ADMIN, HOT_WALLET};
+    use crate::tests_helpers::instantiate_contract;
+    use crate::ContractError;
+
+    const RELAYER: &str = "relayer";
+    const FEE_LENDER: &str = "test_repay_address";
+    const RECEIVER: &str = "diane";
+
+    type MockDeps = OwnedDeps<MemoryStorage, MockApi, MockQuerier<Empty>, Empty>;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32]).unwrap()
+    }
+
+    fn pubkey(key: &SigningKey) -> Binary {
+        Binary::from(key.verifying_key().to_bytes().to_vec())
+    }
+
+    // k256 hashes the message with sha256, matching sign_bytes
+    fn sign(key: &SigningKey, payload: &SignedPayload) -> Binary {
+        let signature: Signature = key.sign(&to_vec(payload).unwrap());
+        Binary::from(signature.as_ref().to_vec())
+    }
+
+    fn payload(env: &Env, nonce: u64, amount: u128) -> SignedPayload {
+        SignedPayload {
+            wallet: HOT_WALLET.to_string(),
+            proxy: env.contract.address.to_string(),
+            chain_id: env.block.chain_id.clone(),
+            nonce,
+            expires: env.block.time.plus_seconds(600),
+            msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: RECEIVER.to_string(),
+                amount: coins(amount, "testtokens"),
+            })],
+            relayer_fee: None,
+        }
+    }
+
+    fn setup() -> MockDeps {
+        let mut deps = mock_dependencies();
+        instantiate_contract(
+            &mut deps,
+            mock_env(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::SetSigningKey {
+                pubkey: pubkey(&signing_key()),
+            },
+        )
+        .unwrap();
+        deps
+    }
+
+    fn relay(
+        deps: &mut MockDeps,
+        relayer: &str,
+        payload: SignedPayload,
+        signature: Binary,
+    ) -> Result<Response, ContractError> {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(relayer, &[]),
+            ExecuteMsg::ExecuteSigned {
+                payload,
+                pubkey: pubkey(&signing_key()),
+                signature,
+            },
+        )
+    }
+
+    #[test]
+    fn only_hot_wallets_set_signing_keys() {
+        let mut deps = setup();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SetSigningKey {
+                pubkey: pubkey(&signing_key()),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(HOT_WALLET, &[]),
+            ExecuteMsg::SetSigningKey {
+                pubkey: Binary::from(vec![2u8; 20]),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidSigningKey {});
+
+        // removing the hot wallet drops its key
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::RmHotWallet {
+                doomed_hot_wallet: HOT_WALLET.to_string(),
+            },
+        )
+        .unwrap();
+        let res = query_signed_nonce(deps.as_ref(), HOT_WALLET.to_string()).unwrap();
+        assert_eq!(res.pubkey, None);
+    }
+
+    #[test]
+    fn relayer_executes_signed_batch_once() {
+        let mut deps = setup();
+        let env = mock_env();
+        let signed = payload(&env, 0, 9_000);
+        let signature = sign(&signing_key(), &signed);
+
+        let res = relay(&mut deps, RELAYER, signed.clone(), signature.clone()).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let event = res
+            .events
+            .iter()
+            .find(|event| event.ty == SIGNED_EXECUTE_EVENT)
+            .unwrap();
+        assert_eq!(event.attributes[1].value, RELAYER);
+        assert_eq!(event.attributes[2].value, HOT_WALLET);
+        let res = query_signed_nonce(deps.as_ref(), HOT_WALLET.to_string()).unwrap();
+        assert_eq!(res.next_nonce, 1);
+        assert_eq!(res.pubkey, Some(pubkey(&signing_key())));
+
+        // the same payload can't go through twice
+        let err = relay(&mut deps, RELAYER, signed, signature).unwrap_err();
+        assert_eq!(err, ContractError::BadNonce(1, 0));
+
+        // and the spend counts toward the hot wallet's limit
+        let signed = payload(&env, 1, 2_000);
+        let signature = sign(&signing_key(), &signed);
+        let err = relay(&mut deps, RELAYER, signed, signature).unwrap_err();
+        assert!(matches!(err, ContractError::CannotSpendMoreThanLimit(..)));
+    }
+
+    #[test]
+    fn rejects_bad_signatures_and_stale_payloads() {
+        let mut deps = setup();
+        let env = mock_env();
+
+        // signed by another key
+        let other_key = SigningKey::from_bytes(&[8u8; 32]).unwrap();
+        let signed = payload(&env, 0, 1_000);
+        let err = relay(
+            &mut deps,
+            RELAYER,
+            signed.clone(),
+            sign(&other_key, &signed),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature {});
+
+        // tampered with after signing
+        let signature = sign(&signing_key(), &signed);
+        let mut tampered = signed.clone();
+        tampered.msgs = payload(&env, 0, 9_000).msgs;
+        let err = relay(&mut deps, RELAYER, tampered, signature).unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature {});
+
+        // for another chain
+        let mut other_chain = signed.clone();
+        other_chain.chain_id = "juno-1".to_string();
+        let signature = sign(&signing_key(), &other_chain);
+        let err = relay(&mut deps, RELAYER, other_chain, signature).unwrap_err();
+        assert_eq!(err, ContractError::WrongSignedPayloadTarget {});
+
+        // expired
+        let mut expired = signed;
+        expired.expires = env.block.time;
+        let signature = sign(&signing_key(), &expired);
+        let err = relay(&mut deps, RELAYER, expired, signature).unwrap_err();
+        assert_eq!(err, ContractError::SignedPayloadExpired {});
+
+        // no nonce was used up
+        let res = query_signed_nonce(deps.as_ref(), HOT_WALLET.to_string()).unwrap();
+        assert_eq!(res.next_nonce, 0);
+    }
+
+    #[test]
+    fn relayer_fee_is_added_to_fee_debt() {
+        let mut deps = setup();
+        let env = mock_env();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SetMaxFeeDebt {
+                max_fee_debt: Uint128::from(50_000u128),
+            },
+        )
+        .unwrap();
+        let mut signed = payload(&env, 0, 1_000);
+        signed.relayer_fee = Some(Uint128::from(30_000u128));
+        let signature = sign(&signing_key(), &signed);
+
+        // only the fee lender can relay for a fee. Anyone else is
+        // rejected rather than relaying it for free
+        let err = relay(&mut deps, RELAYER, signed.clone(), signature.clone()).unwrap_err();
+        assert_eq!(err, ContractError::CallerIsNotFeeLender {});
+        let res = query_signed_nonce(deps.as_ref(), HOT_WALLET.to_string()).unwrap();
+        assert_eq!(res.next_nonce, 0);
+
+        let res = relay(&mut deps, FEE_LENDER, signed, signature).unwrap();
+        let message_count = res.messages.len();
+        let event = res
+            .events
+            .iter()
+            .find(|event| event.ty == SIGNED_EXECUTE_EVENT)
+            .unwrap();
+        assert_eq!(event.attributes[4].value, "30000");
+        let res = query_fee_debt_history(deps.as_ref(), None, None).unwrap();
+        assert_eq!(res.records[0].1.amount, Uint128::from(30_000u128));
+        assert_eq!(res.records[0].1.memo, "execute_signed".to_string());
+
+        // by default, the batch's own spend repays it
+        assert_eq!(message_count, 2);
+        let res = query_fee_debt(deps.as_ref()).unwrap();
+        assert_eq!(res.uusd_fee_debt, Uint128::from(0u128));
+    }
+}
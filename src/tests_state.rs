@@ -4,9 +4,11 @@ mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env};
     use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
 
+    use crate::constants::MAINNET_AXLUSDC_IBC;
     use crate::hot_wallet::{CoinLimit, HotWallet, PeriodType};
     use crate::pair_contract_defaults::get_local_pair_contracts;
     use crate::state::State;
+    use crate::ContractError;
 
     #[test]
     fn is_admin() {
@@ -14,8 +16,14 @@ mod tests {
         let config = State {
             admin: Addr::unchecked(admin),
             pending: Addr::unchecked(admin),
+            admin_transfer_delay: None,
+            admin_transfer_window: None,
+            frozen_by: None,
             hot_wallets: vec![],
             uusd_fee_debt: Uint128::from(0u128),
+            max_fee_debt: None,
+            fee_repay_policy: None,
+            proposal_threshold: None,
             fee_lend_repay_wallet: Addr::unchecked("test_repay_address"),
             home_network: "local".to_string(),
             pair_contracts: get_local_pair_contracts().to_vec(),
@@ -41,6 +49,9 @@ mod tests {
         let mut config = State {
             admin: Addr::unchecked(admin),
             pending: Addr::unchecked(admin),
+            admin_transfer_delay: None,
+            admin_transfer_window: None,
+            frozen_by: None,
             hot_wallets: vec![HotWallet {
                 address: spender.to_string(),
                 current_period_reset: dt.timestamp() as u64,
@@ -54,8 +65,16 @@ mod tests {
                 }],
                 usdc_denom: Some("true".to_string()),
                 default: Some(true),
+                paused: None,
+                max_tx_per_period: None,
+                min_seconds_between_tx: None,
+                tx_count: None,
+                last_tx_time: None,
             }],
             uusd_fee_debt: Uint128::from(0u128),
+            max_fee_debt: None,
+            fee_repay_policy: None,
+            proposal_threshold: None,
             fee_lend_repay_wallet: Addr::unchecked("test_repay_address"),
             home_network: "local".to_string(),
             pair_contracts: get_local_pair_contracts().to_vec(),
@@ -153,6 +172,9 @@ mod tests {
         let mut config = State {
             admin: Addr::unchecked(admin),
             pending: Addr::unchecked(admin),
+            admin_transfer_delay: None,
+            admin_transfer_window: None,
+            frozen_by: None,
             hot_wallets: vec![HotWallet {
                 address: spender.to_string(),
                 current_period_reset: dt.timestamp() as u64,
@@ -166,8 +188,16 @@ mod tests {
                 }],
                 usdc_denom: None, // 100 JUNO, 100 axlUSDC, 9000 LOOP
                 default: Some(true),
+                paused: None,
+                max_tx_per_period: None,
+                min_seconds_between_tx: None,
+                tx_count: None,
+                last_tx_time: None,
             }],
             uusd_fee_debt: Uint128::from(0u128),
+            max_fee_debt: None,
+            fee_repay_policy: None,
+            proposal_threshold: None,
             fee_lend_repay_wallet: Addr::unchecked("test_repay_address"),
             home_network: "local".to_string(),
             pair_contracts: get_local_pair_contracts().to_vec(),
@@ -230,4 +260,93 @@ mod tests {
             )
             .unwrap();
     }
+
+    #[test]
+    fn check_and_update_tx_limits() {
+        let deps = mock_dependencies();
+        let spender: &str = "owen";
+        let start = mock_env().block.time;
+        let usdc = |amount: u128| {
+            vec![Coin {
+                denom: MAINNET_AXLUSDC_IBC.to_string(),
+                amount: Uint128::from(amount),
+            }]
+        };
+        let hot_wallet = HotWallet {
+            address: spender.to_string(),
+            current_period_reset: start.seconds() + 86_400,
+            period_type: PeriodType::DAYS,
+            period_multiple: 1,
+            spend_limits: vec![CoinLimit {
+                amount: 100_000_000u64,
+                denom: MAINNET_AXLUSDC_IBC.to_string(),
+                limit_remaining: 100_000_000u64,
+            }],
+            usdc_denom: Some("true".to_string()),
+            default: Some(true),
+            paused: None,
+            max_tx_per_period: Some(2),
+            min_seconds_between_tx: Some(60),
+            tx_count: None,
+            last_tx_time: None,
+        };
+        hot_wallet.assert_is_valid().unwrap();
+        let mut config = State {
+            admin: Addr::unchecked("bob"),
+            pending: Addr::unchecked("bob"),
+            admin_transfer_delay: None,
+            admin_transfer_window: None,
+            frozen_by: None,
+            hot_wallets: vec![HotWallet {
+                max_tx_per_period: Some(0),
+                ..hot_wallet.clone()
+            }],
+            uusd_fee_debt: Uint128::from(0u128),
+            max_fee_debt: None,
+            fee_repay_policy: None,
+            proposal_threshold: None,
+            fee_lend_repay_wallet: Addr::unchecked("test_repay_address"),
+            home_network: "local".to_string(),
+            pair_contracts: get_local_pair_contracts().to_vec(),
+        };
+        config.hot_wallets[0].assert_is_valid().unwrap_err();
+        config.hot_wallets = vec![hot_wallet];
+        // a transaction as execute_execute runs it
+        let mut spend_at = |seconds: u64| {
+            let now = start.plus_seconds(seconds);
+            config.check_tx_limits(now, spender.to_string())?;
+            config.check_and_update_spend_limits(
+                deps.as_ref(),
+                now,
+                spender.to_string(),
+                usdc(1_000_000),
+            )?;
+            config.record_tx(now, spender.to_string())
+        };
+
+        // small spends still need a minute between them
+        spend_at(0).unwrap();
+        assert_eq!(
+            spend_at(30).unwrap_err(),
+            ContractError::TransactionTooSoon(start.seconds() + 60)
+        );
+        spend_at(60).unwrap();
+
+        // and there are only two a day
+        assert_eq!(
+            spend_at(120).unwrap_err(),
+            ContractError::TooManyTransactions(2)
+        );
+        let next_day = start.plus_seconds(86_401);
+        config
+            .check_spend_limits(deps.as_ref(), next_day, spender.to_string(), usdc(1))
+            .unwrap();
+        config
+            .check_and_update_spend_limits(deps.as_ref(), next_day, spender.to_string(), usdc(1))
+            .unwrap();
+        assert_eq!(config.hot_wallets[0].tx_count, None);
+        config.record_tx(next_day, spender.to_string()).unwrap();
+        assert_eq!(config.hot_wallets[0].tx_count, Some(1));
+        assert_eq!(config.hot_wallets[0].last_tx_time, Some(next_day.seconds()));
+    }
 }
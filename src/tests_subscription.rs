@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coins, BankMsg, Coin, CosmosMsg, Timestamp, Uint128};
+
+    use crate::contract::{execute, query_subscriptions};
+    use crate::hot_wallet::PeriodType;
+    use crate::msg::ExecuteMsg;
+    use crate::subscription::Subscription;
+    use crate::tests_contract::{ADMIN, HOT_WALLET};
+    use crate::tests_helpers::instantiate_contract;
+    use crate::ContractError;
+
+    const VENDOR: &str = "vendor";
+    const KEEPER: &str = "keeper";
+    // the first of November and December 2019, after mock_env's block time
+    const NOVEMBER_1: u64 = 1_572_566_400;
+    const DECEMBER_1: u64 = 1_575_158_400;
+
+    fn monthly_subscription() -> Subscription {
+        Subscription {
+            recipient: VENDOR.to_string(),
+            amount: coins(5_000, "testtokens"),
+            period: PeriodType::MONTHS,
+            period_multiple: 1,
+            // the day after mock_env's block time
+            next_due: mock_env().block.time.plus_seconds(86_400),
+            end: Some(Timestamp::from_seconds(DECEMBER_1)),
+        }
+    }
+
+    #[test]
+    fn subscription_advances_like_hot_wallet_reset() {
+        let mut subscription = monthly_subscription();
+        assert!(!subscription.is_due(mock_env().block.time));
+        assert!(subscription.is_due(subscription.next_due));
+        subscription.advance().unwrap();
+        assert_eq!(subscription.next_due, Timestamp::from_seconds(NOVEMBER_1));
+        assert!(!subscription.is_finished());
+        subscription.advance().unwrap();
+        assert!(subscription.is_finished());
+        assert!(!subscription.is_due(Timestamp::from_seconds(DECEMBER_1)));
+
+        subscription.period_multiple = 0;
+        subscription.assert_is_valid().unwrap_err();
+    }
+
+    #[test]
+    fn anyone_triggers_due_payments() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        instantiate_contract(
+            &mut deps,
+            env.clone(),
+            Coin {
+                amount: Uint128::from(0u128),
+                denom: "ujunox".to_string(),
+            },
+        );
+        let add_msg = ExecuteMsg::AddSubscription {
+            subscription: monthly_subscription(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(HOT_WALLET, &[]),
+            add_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::AddSubscription {
+                subscription: Subscription {
+                    next_due: env.block.time,
+                    ..monthly_subscription()
+                },
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::SubscriptionInPast {});
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::AddSubscription {
+                subscription: Subscription {
+                    end: Some(monthly_subscription().next_due),
+                    ..monthly_subscription()
+                },
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::SubscriptionEndsBeforeFirstPayment {});
+        execute(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), add_msg).unwrap();
+        let trigger_msg = ExecuteMsg::TriggerPayments { ids: vec![0, 0, 7] };
+
+        // unknown ids are skipped, and a repeated id is paid once
+        env.block.time = monthly_subscription().next_due;
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER, &[]),
+            trigger_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: VENDOR.to_string(),
+                amount: coins(5_000, "testtokens"),
+            })
+        );
+        assert_eq!(res.messages.len(), 1);
+        let res = query_subscriptions(deps.as_ref(), None, None).unwrap();
+        assert_eq!(
+            res.subscriptions[0].1.next_due,
+            Timestamp::from_seconds(NOVEMBER_1)
+        );
+
+        // not due again until November
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER, &[]),
+            trigger_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoPaymentsDue {});
+        env.block.time = Timestamp::from_seconds(NOVEMBER_1);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::Freeze {},
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER, &[]),
+            trigger_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Frozen {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::Unfreeze {},
+        )
+        .unwrap();
+
+        // the November payment is the last before the end
+        let res = execute(deps.as_mut(), env, mock_info(KEEPER, &[]), trigger_msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let res = query_subscriptions(deps.as_ref(), None, None).unwrap();
+        assert!(res.subscriptions.is_empty());
+    }
+}